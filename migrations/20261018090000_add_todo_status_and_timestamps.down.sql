@@ -0,0 +1,8 @@
+-- Add down migration script here
+
+ALTER TABLE todos
+  DROP COLUMN IF EXISTS completed,
+  DROP COLUMN IF EXISTS completed_at,
+  DROP COLUMN IF EXISTS due_at,
+  DROP COLUMN IF EXISTS created_at,
+  DROP COLUMN IF EXISTS updated_at;
@@ -0,0 +1,6 @@
+ALTER TABLE todos
+  ADD COLUMN completed BOOLEAN NOT NULL DEFAULT FALSE,
+  ADD COLUMN completed_at TIMESTAMPTZ,
+  ADD COLUMN due_at TIMESTAMPTZ,
+  ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  ADD COLUMN updated_at TIMESTAMPTZ NOT NULL DEFAULT now();
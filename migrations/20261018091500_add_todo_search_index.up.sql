@@ -0,0 +1,6 @@
+ALTER TABLE todos
+  ADD COLUMN search TSVECTOR GENERATED ALWAYS AS (
+    to_tsvector('english', title || ' ' || content)
+  ) STORED;
+
+CREATE INDEX IF NOT EXISTS todos_search_idx ON todos USING GIN (search);
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS users (
+  id SERIAL PRIMARY KEY,
+  email VARCHAR(255) NOT NULL UNIQUE,
+  password_hash TEXT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Todos created before accounts existed stay unowned and are therefore not
+-- visible to any user.
+ALTER TABLE todos
+  ADD COLUMN owner_id INTEGER REFERENCES users (id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS todos_owner_id_idx ON todos (owner_id);
@@ -0,0 +1,17 @@
+CREATE TYPE api_key_scope AS ENUM ('read_only', 'read_write');
+
+-- Only a hash of each key is stored; `prefix` keeps enough of the key for
+-- users to tell their keys apart.
+CREATE TABLE IF NOT EXISTS api_keys (
+  id SERIAL PRIMARY KEY,
+  user_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+  name VARCHAR(100) NOT NULL,
+  prefix VARCHAR(16) NOT NULL,
+  key_hash TEXT NOT NULL UNIQUE,
+  scope api_key_scope NOT NULL,
+  last_used_at TIMESTAMPTZ,
+  revoked_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS api_keys_user_id_idx ON api_keys (user_id);
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS lists (
+  id SERIAL PRIMARY KEY,
+  owner_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+  name VARCHAR(255) NOT NULL,
+  color CHAR(7),
+  archived BOOLEAN NOT NULL DEFAULT false,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS lists_owner_id_idx ON lists (owner_id);
+
+-- Deleting a list deletes its todos unless they were reassigned beforehand,
+-- see `ListService::delete`.
+ALTER TABLE todos
+  ADD COLUMN list_id INTEGER REFERENCES lists (id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS todos_list_id_idx ON todos (list_id);
@@ -0,0 +1,12 @@
+-- The uniqueness of positions is only checked at commit so items can be
+-- renumbered one row at a time while reordering.
+CREATE TABLE IF NOT EXISTS todo_items (
+  id SERIAL PRIMARY KEY,
+  todo_id INTEGER NOT NULL REFERENCES todos (id) ON DELETE CASCADE,
+  title VARCHAR(255) NOT NULL,
+  done BOOLEAN NOT NULL DEFAULT false,
+  position INTEGER NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE (todo_id, position) DEFERRABLE INITIALLY DEFERRED
+);
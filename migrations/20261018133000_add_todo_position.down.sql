@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS todos_owner_id_position_idx;
+
+ALTER TABLE todos
+  DROP COLUMN IF EXISTS position;
@@ -0,0 +1,13 @@
+-- Positions are spread 1024 apart per owner so a todo can be moved between
+-- two others by taking the midpoint, without touching any other row.
+ALTER TABLE todos
+  ADD COLUMN position BIGINT;
+
+UPDATE todos SET position = ranked.rank * 1024
+FROM (SELECT id, ROW_NUMBER() OVER (PARTITION BY owner_id ORDER BY id) AS rank FROM todos) AS ranked
+WHERE todos.id = ranked.id;
+
+ALTER TABLE todos
+  ALTER COLUMN position SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS todos_owner_id_position_idx ON todos (owner_id, position, id);
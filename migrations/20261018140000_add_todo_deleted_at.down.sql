@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS todos_deleted_at_idx;
+
+ALTER TABLE todos
+  DROP COLUMN IF EXISTS deleted_at;
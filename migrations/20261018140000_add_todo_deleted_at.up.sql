@@ -0,0 +1,5 @@
+-- Deleted todos stay in the table until they are purged, see `TrashSettings`.
+ALTER TABLE todos
+  ADD COLUMN deleted_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS todos_deleted_at_idx ON todos (deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,4 @@
+-- Bumped on every change so clients can detect concurrent edits through
+-- the ETag of a todo.
+ALTER TABLE todos
+  ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
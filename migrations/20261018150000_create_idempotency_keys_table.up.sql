@@ -0,0 +1,13 @@
+-- Responses to requests sent with an `Idempotency-Key` header, replayed when
+-- a client retries the same request. A row without a status code belongs to
+-- a request that is still being handled.
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+  user_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+  key VARCHAR(255) NOT NULL,
+  fingerprint TEXT NOT NULL,
+  status_code SMALLINT,
+  headers TEXT[] NOT NULL DEFAULT '{}',
+  body BYTEA,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  PRIMARY KEY (user_id, key)
+);
@@ -0,0 +1,4 @@
+ALTER TABLE todos
+  DROP CONSTRAINT IF EXISTS todos_list_id_fkey,
+  ADD CONSTRAINT todos_list_id_fkey
+    FOREIGN KEY (list_id) REFERENCES lists (id) ON DELETE CASCADE;
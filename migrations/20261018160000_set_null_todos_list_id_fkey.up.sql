@@ -0,0 +1,6 @@
+-- Deleting a list moves its todos to the trash and detaches them, see
+-- `ListService::delete`. The foreign key must never delete them instead.
+ALTER TABLE todos
+  DROP CONSTRAINT IF EXISTS todos_list_id_fkey,
+  ADD CONSTRAINT todos_list_id_fkey
+    FOREIGN KEY (list_id) REFERENCES lists (id) ON DELETE SET NULL;
@@ -1,9 +1,13 @@
 use sqlx::{postgres::PgPoolOptions, Postgres};
 
-pub async fn new_pg_pool(database_url: &str) -> sqlx::Pool<Postgres> {
+use super::settings::DatabaseSettings;
+
+pub async fn new_pg_pool(settings: &DatabaseSettings) -> sqlx::Pool<Postgres> {
     PgPoolOptions::new()
-        .max_connections(50)
-        .connect(database_url)
+        .max_connections(settings.max_connections)
+        .min_connections(settings.min_connections)
+        .acquire_timeout(settings.acquire_timeout)
+        .connect(&settings.url)
         .await
         .unwrap_or_else(|error| panic!("Could not build connection pool: {}", error))
 }
@@ -0,0 +1,110 @@
+use std::collections::HashSet;
+
+use sqlx::{
+    migrate::{MigrateError, Migrator},
+    Postgres,
+};
+
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+#[derive(Debug, PartialEq)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub applied: bool,
+}
+
+// Only reads, as the readiness probe calls it on every poll. Without the
+// migrations table, nothing is applied yet.
+pub async fn applied_versions(db_pool: &sqlx::Pool<Postgres>) -> Result<Vec<i64>, MigrateError> {
+    let has_table = sqlx::query_scalar::<_, bool>(
+        "SELECT to_regclass('_sqlx_migrations') IS NOT NULL;",
+    )
+    .fetch_one(db_pool)
+    .await?;
+    if !has_table {
+        return Ok(Vec::new());
+    }
+    Ok(
+        sqlx::query_scalar::<_, i64>("SELECT version FROM _sqlx_migrations ORDER BY version;")
+            .fetch_all(db_pool)
+            .await?,
+    )
+}
+
+pub async fn ensure_schema_not_ahead(db_pool: &sqlx::Pool<Postgres>) -> Result<(), MigrateError> {
+    let known: HashSet<i64> = MIGRATOR.iter().map(|migration| migration.version).collect();
+    match applied_versions(db_pool)
+        .await?
+        .into_iter()
+        .find(|version| !known.contains(version))
+    {
+        Some(version) => Err(MigrateError::VersionMissing(version)),
+        None => Ok(()),
+    }
+}
+
+pub async fn run(db_pool: &sqlx::Pool<Postgres>) -> Result<(), MigrateError> {
+    ensure_schema_not_ahead(db_pool).await?;
+    MIGRATOR.run(db_pool).await
+}
+
+pub async fn revert_last(db_pool: &sqlx::Pool<Postgres>) -> Result<Option<i64>, MigrateError> {
+    ensure_schema_not_ahead(db_pool).await?;
+    let mut applied = applied_versions(db_pool).await?;
+    applied.sort_unstable();
+    let last = match applied.pop() {
+        None => return Ok(None),
+        Some(version) => version,
+    };
+    MIGRATOR.undo(db_pool, applied.pop().unwrap_or(0)).await?;
+    Ok(Some(last))
+}
+
+pub async fn status(db_pool: &sqlx::Pool<Postgres>) -> Result<Vec<MigrationStatus>, MigrateError> {
+    let applied: HashSet<i64> = applied_versions(db_pool).await?.into_iter().collect();
+    Ok(MIGRATOR
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| MigrationStatus {
+            version: migration.version,
+            description: migration.description.to_string(),
+            applied: applied.contains(&migration.version),
+        })
+        .collect())
+}
+
+pub async fn run_command(db_pool: &sqlx::Pool<Postgres>, action: Option<&str>) -> Result<(), String> {
+    match action {
+        Some("up") => {
+            run(db_pool).await.map_err(describe_error)?;
+            println!("Migrations are up to date");
+        }
+        Some("down") => match revert_last(db_pool).await.map_err(describe_error)? {
+            None => println!("No migration to revert"),
+            Some(version) => println!("Reverted migration {}", version),
+        },
+        Some("status") => {
+            for migration in status(db_pool).await.map_err(describe_error)? {
+                println!(
+                    "{}\t{}\t{}",
+                    migration.version,
+                    if migration.applied { "applied" } else { "pending" },
+                    migration.description
+                );
+            }
+        }
+        _ => return Err("usage: todos-with-axum migrate <up|down|status>".to_string()),
+    }
+    Ok(())
+}
+
+pub fn describe_error(error: MigrateError) -> String {
+    match error {
+        MigrateError::VersionMissing(version) => format!(
+            "database schema is ahead of this binary: migration {} is applied but unknown to this build",
+            version
+        ),
+        error => format!("could not migrate database: {}", error),
+    }
+}
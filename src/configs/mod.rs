@@ -1,2 +1,5 @@
 pub mod db;
+pub mod migrations;
+pub mod pagination;
+pub mod settings;
 pub mod state;
\ No newline at end of file
@@ -0,0 +1,193 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Deserialize;
+
+use crate::utils::error::AppError;
+use crate::views::errors::BadRequestErrorMessage;
+
+const DEFAULT_SUFFIX: &str = "PAGE_SIZE_DEFAULT";
+const MAX_SUFFIX: &str = "PAGE_SIZE_MAX";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageSizeLimits {
+    pub default: i64,
+    pub max: i64,
+}
+
+impl Default for PageSizeLimits {
+    fn default() -> Self {
+        PageSizeLimits { default: 10, max: 10 }
+    }
+}
+
+impl PageSizeLimits {
+    pub fn new(default: i64, max: i64) -> Result<Self, String> {
+        if default < 1 || max < 1 {
+            return Err(format!(
+                "page sizes must be positive, got default {} and max {}",
+                default, max
+            ));
+        }
+        if default > max {
+            return Err(format!(
+                "default page size {} is greater than max page size {}",
+                default, max
+            ));
+        }
+        Ok(PageSizeLimits { default, max })
+    }
+
+    fn merge(&self, overrides: PageSizeOverrides) -> Result<Self, String> {
+        PageSizeLimits::new(
+            overrides.default.unwrap_or(self.default),
+            overrides.max.unwrap_or(self.max),
+        )
+    }
+
+    // Returns the limit to apply and, when the requested limit had to be
+    // reduced to the maximum, the limit that was originally requested.
+    pub fn resolve(&self, requested: Option<i64>) -> (i64, Option<i64>) {
+        match requested {
+            None => (self.default, None),
+            Some(requested) if requested > self.max => (self.max, Some(requested)),
+            Some(requested) => (requested, None),
+        }
+    }
+
+    // Like `resolve`, for the `limit` and `offset` query parameters of a
+    // listing, which are rejected when out of range rather than clamped.
+    pub fn resolve_query(
+        &self,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<(i64, Option<i64>), AppError> {
+        if limit.is_some_and(|limit| limit < 1) {
+            return Err(AppError::BadRequest(BadRequestErrorMessage::invalid_query(
+                "limit".to_string(),
+                "must be a positive integer".to_string(),
+            )));
+        }
+        if offset.is_some_and(|offset| offset < 0) {
+            return Err(AppError::BadRequest(BadRequestErrorMessage::invalid_query(
+                "offset".to_string(),
+                "must not be negative".to_string(),
+            )));
+        }
+        Ok(self.resolve(limit))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PageSizeOverrides {
+    pub default: Option<i64>,
+    pub max: Option<i64>,
+}
+
+impl PageSizeOverrides {
+    fn or(self, fallback: PageSizeOverrides) -> PageSizeOverrides {
+        PageSizeOverrides {
+            default: self.default.or(fallback.default),
+            max: self.max.or(fallback.max),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PaginationFile {
+    pub default: Option<i64>,
+    pub max: Option<i64>,
+    #[serde(default)]
+    pub modules: HashMap<String, PageSizeOverrides>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PaginationConfig {
+    pub default: PageSizeLimits,
+    pub modules: HashMap<String, PageSizeLimits>,
+}
+
+impl PaginationConfig {
+    pub fn for_module(&self, module: &str) -> PageSizeLimits {
+        self.modules.get(module).copied().unwrap_or(self.default)
+    }
+
+    // Environment variables take precedence over the configuration file:
+    // `PAGE_SIZE_DEFAULT`/`PAGE_SIZE_MAX` apply to every module and
+    // `<MODULE>_PAGE_SIZE_DEFAULT`/`<MODULE>_PAGE_SIZE_MAX` to a single one,
+    // e.g. `TODOS_PAGE_SIZE_MAX=50`.
+    pub fn from_sources(
+        file: &PaginationFile,
+        vars: &HashMap<String, String>,
+    ) -> Result<Self, Vec<String>> {
+        let mut errors: Vec<String> = vec![];
+        let mut env_overrides: HashMap<String, PageSizeOverrides> = HashMap::new();
+        for (key, value) in vars.iter() {
+            let (prefix, is_default) = match (
+                key.strip_suffix(DEFAULT_SUFFIX),
+                key.strip_suffix(MAX_SUFFIX),
+            ) {
+                (Some(prefix), _) => (prefix, true),
+                (_, Some(prefix)) => (prefix, false),
+                _ => continue,
+            };
+            let module = match prefix {
+                "" => String::new(),
+                prefix => match prefix.strip_suffix('_') {
+                    Some(module) => module.to_lowercase(),
+                    None => continue,
+                },
+            };
+            let value = match value.parse::<i64>() {
+                Ok(value) => value,
+                Err(_) => {
+                    errors.push(format!("{} must be an integer, got {:?}", key, value));
+                    continue;
+                }
+            };
+            let entry = env_overrides.entry(module).or_default();
+            match is_default {
+                true => entry.default = Some(value),
+                false => entry.max = Some(value),
+            }
+        }
+
+        let file_default = PageSizeOverrides {
+            default: file.default,
+            max: file.max,
+        };
+        let default = env_overrides
+            .remove("")
+            .unwrap_or_default()
+            .or(file_default);
+        let default = match PageSizeLimits::default().merge(default) {
+            Ok(limits) => limits,
+            Err(error) => {
+                errors.push(format!("pagination: {}", error));
+                PageSizeLimits::default()
+            }
+        };
+
+        let mut modules = HashMap::new();
+        let names: HashSet<&String> = file.modules.keys().chain(env_overrides.keys()).collect();
+        for module in names {
+            let overrides = env_overrides
+                .get(module)
+                .copied()
+                .unwrap_or_default()
+                .or(file.modules.get(module).copied().unwrap_or_default());
+            match default.merge(overrides) {
+                Ok(limits) => {
+                    modules.insert(module.to_string(), limits);
+                }
+                Err(error) => errors.push(format!("pagination.{}: {}", module, error)),
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(PaginationConfig { default, modules }),
+            false => Err(errors),
+        }
+    }
+}
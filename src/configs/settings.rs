@@ -0,0 +1,514 @@
+use std::{collections::HashMap, env, fs, path::Path, str::FromStr, time::Duration};
+
+use serde::Deserialize;
+
+use super::pagination::{PaginationConfig, PaginationFile};
+use crate::utils::jwt::JwtKeys;
+
+const DEFAULT_CONFIG_FILE: &str = "config.toml";
+const MIN_JWT_SECRET_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerSettings {
+    pub host: String,
+    pub port: u16,
+    pub shutdown_timeout: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseSettings {
+    pub url: String,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JwtAlgorithm {
+    Hs256,
+    Rs256,
+}
+
+impl FromStr for JwtAlgorithm {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_uppercase().as_str() {
+            "HS256" => Ok(JwtAlgorithm::Hs256),
+            "RS256" => Ok(JwtAlgorithm::Rs256),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthSettings {
+    pub algorithm: JwtAlgorithm,
+    // Shared secret for HS256.
+    pub secret: String,
+    // PEM encoded key pair for RS256.
+    pub private_key: String,
+    pub public_key: String,
+    pub issuer: String,
+    pub audience: String,
+    pub access_token_ttl: Duration,
+    pub refresh_token_ttl: Duration,
+    pub leeway: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrashSettings {
+    // How long deleted todos can be restored before they are purged.
+    pub retention: Duration,
+    pub purge_interval: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotencySettings {
+    // How long a response is replayed for retries with the same key.
+    pub ttl: Duration,
+    // How long a key stays claimed by a request that never finished, e.g.
+    // because the process died, before a retry may claim it again.
+    pub lock_timeout: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkSettings {
+    // Upper bound on the operations of a single `POST /todos/bulk`.
+    pub max_operations: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub server: ServerSettings,
+    pub database: DatabaseSettings,
+    pub auth: AuthSettings,
+    pub trash: TrashSettings,
+    pub idempotency: IdempotencySettings,
+    pub bulk: BulkSettings,
+    pub migrate_on_start: bool,
+    pub pagination: PaginationConfig,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            server: ServerSettings {
+                host: "0.0.0.0".to_string(),
+                port: 3000,
+                shutdown_timeout: Duration::from_secs(30),
+            },
+            database: DatabaseSettings {
+                url: String::new(),
+                max_connections: 50,
+                min_connections: 0,
+                acquire_timeout: Duration::from_secs(30),
+            },
+            auth: AuthSettings {
+                algorithm: JwtAlgorithm::Hs256,
+                secret: String::new(),
+                private_key: String::new(),
+                public_key: String::new(),
+                issuer: "todos-with-axum".to_string(),
+                audience: "todos-with-axum".to_string(),
+                access_token_ttl: Duration::from_secs(15 * 60),
+                refresh_token_ttl: Duration::from_secs(30 * 24 * 60 * 60),
+                leeway: Duration::from_secs(30),
+            },
+            trash: TrashSettings {
+                retention: Duration::from_secs(30 * 24 * 60 * 60),
+                purge_interval: Duration::from_secs(60 * 60),
+            },
+            idempotency: IdempotencySettings {
+                ttl: Duration::from_secs(24 * 60 * 60),
+                lock_timeout: Duration::from_secs(60),
+            },
+            bulk: BulkSettings { max_operations: 100 },
+            migrate_on_start: true,
+            pagination: PaginationConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ServerFile {
+    host: Option<String>,
+    port: Option<u16>,
+    shutdown_timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DatabaseFile {
+    url: Option<String>,
+    max_connections: Option<u32>,
+    min_connections: Option<u32>,
+    acquire_timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AuthFile {
+    algorithm: Option<String>,
+    secret: Option<String>,
+    private_key_file: Option<String>,
+    public_key_file: Option<String>,
+    issuer: Option<String>,
+    audience: Option<String>,
+    access_token_ttl_secs: Option<u64>,
+    refresh_token_ttl_secs: Option<u64>,
+    leeway_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TrashFile {
+    retention_secs: Option<u64>,
+    purge_interval_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IdempotencyFile {
+    ttl_secs: Option<u64>,
+    lock_timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BulkFile {
+    max_operations: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SettingsFile {
+    #[serde(default)]
+    server: ServerFile,
+    #[serde(default)]
+    database: DatabaseFile,
+    #[serde(default)]
+    auth: AuthFile,
+    #[serde(default)]
+    trash: TrashFile,
+    #[serde(default)]
+    idempotency: IdempotencyFile,
+    #[serde(default)]
+    bulk: BulkFile,
+    migrate_on_start: Option<bool>,
+    #[serde(default)]
+    pagination: PaginationFile,
+}
+
+struct Layers<'a> {
+    vars: &'a HashMap<String, String>,
+    errors: Vec<String>,
+}
+
+impl<'a> Layers<'a> {
+    // Environment variables win over the configuration file, which wins over
+    // the built-in default. Empty variables count as unset.
+    fn var(&self, key: &str) -> Option<&String> {
+        self.vars.get(key).filter(|value| !value.is_empty())
+    }
+
+    fn pick<T: FromStr>(&mut self, key: &str, file: Option<T>, default: T) -> T {
+        match self.var(key) {
+            None => file.unwrap_or(default),
+            Some(value) => match value.parse::<T>() {
+                Ok(value) => value,
+                Err(_) => {
+                    self.errors.push(format!("{} has an invalid value {:?}", key, value));
+                    default
+                }
+            },
+        }
+    }
+
+    // Reads the file named by `key` (or by the configuration file) and returns
+    // its contents, or an empty string when neither is set.
+    fn read_file(&mut self, key: &str, file: Option<String>) -> String {
+        let path = match self.var(key).cloned().or(file) {
+            None => return String::new(),
+            Some(path) => path,
+        };
+        fs::read_to_string(&path).unwrap_or_else(|error| {
+            self.errors.push(format!("could not read {} {}: {}", key, path, error));
+            String::new()
+        })
+    }
+
+    fn pick_bool(&mut self, key: &str, file: Option<bool>, default: bool) -> bool {
+        match self.var(key).map(|value| value.to_lowercase()) {
+            None => file.unwrap_or(default),
+            Some(value) => match value.as_str() {
+                "true" | "1" | "yes" | "on" => true,
+                "false" | "0" | "no" | "off" => false,
+                _ => {
+                    self.errors.push(format!("{} has an invalid value {:?}", key, value));
+                    default
+                }
+            },
+        }
+    }
+}
+
+// Returns the contents of the configuration file, if there is one.
+fn read_config_file(vars: &HashMap<String, String>) -> Result<Option<String>, Vec<String>> {
+    let path = match vars.get("CONFIG_FILE") {
+        Some(path) => Some(path.to_string()),
+        None => Path::new(DEFAULT_CONFIG_FILE)
+            .exists()
+            .then(|| DEFAULT_CONFIG_FILE.to_string()),
+    };
+    let file = match path {
+        None => None,
+        Some(path) => Some(
+            fs::read_to_string(&path)
+                .map_err(|error| vec![format!("could not read {}: {}", path, error)])?,
+        ),
+    };
+    Ok(file)
+}
+
+fn parse_file(file: Option<&str>) -> Result<SettingsFile, Vec<String>> {
+    match file {
+        None => Ok(SettingsFile::default()),
+        Some(file) => toml::from_str(file)
+            .map_err(|error| vec![format!("invalid configuration file: {}", error)]),
+    }
+}
+
+impl DatabaseSettings {
+    // Only the database settings, for the commands of `main` that must work
+    // without the rest of the configuration, e.g. the JWT secret.
+    pub fn load() -> Result<DatabaseSettings, Vec<String>> {
+        let vars: HashMap<String, String> = env::vars().collect();
+        let file = read_config_file(&vars)?;
+        DatabaseSettings::from_sources(file.as_deref(), &vars)
+    }
+
+    pub fn from_sources(
+        file: Option<&str>,
+        vars: &HashMap<String, String>,
+    ) -> Result<DatabaseSettings, Vec<String>> {
+        let file = parse_file(file)?;
+        let mut layers = Layers {
+            vars,
+            errors: vec![],
+        };
+        let database =
+            DatabaseSettings::from_layers(&mut layers, file.database, Settings::default().database);
+
+        let mut errors = layers.errors;
+        database.validate(&mut errors);
+        match errors.is_empty() {
+            true => Ok(database),
+            false => Err(errors),
+        }
+    }
+
+    fn from_layers(
+        layers: &mut Layers,
+        file: DatabaseFile,
+        defaults: DatabaseSettings,
+    ) -> DatabaseSettings {
+        DatabaseSettings {
+            url: layers.pick("DATABASE_URL", file.url, defaults.url),
+            max_connections: layers.pick(
+                "DATABASE_MAX_CONNECTIONS",
+                file.max_connections,
+                defaults.max_connections,
+            ),
+            min_connections: layers.pick(
+                "DATABASE_MIN_CONNECTIONS",
+                file.min_connections,
+                defaults.min_connections,
+            ),
+            acquire_timeout: Duration::from_secs(layers.pick(
+                "DATABASE_ACQUIRE_TIMEOUT_SECS",
+                file.acquire_timeout_secs,
+                defaults.acquire_timeout.as_secs(),
+            )),
+        }
+    }
+
+    fn validate(&self, errors: &mut Vec<String>) {
+        if self.url.is_empty() {
+            errors.push("DATABASE_URL is required".to_string());
+        } else if !self.url.starts_with("postgres://") && !self.url.starts_with("postgresql://") {
+            errors.push("DATABASE_URL must be a postgres:// url".to_string());
+        }
+        if self.max_connections == 0 {
+            errors.push("DATABASE_MAX_CONNECTIONS must be at least 1".to_string());
+        }
+        if self.min_connections > self.max_connections {
+            errors.push(
+                "DATABASE_MIN_CONNECTIONS must not be greater than DATABASE_MAX_CONNECTIONS"
+                    .to_string(),
+            );
+        }
+        if self.acquire_timeout.is_zero() {
+            errors.push("DATABASE_ACQUIRE_TIMEOUT_SECS must be at least 1".to_string());
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Result<Settings, Vec<String>> {
+        let vars: HashMap<String, String> = env::vars().collect();
+        let file = read_config_file(&vars)?;
+        Settings::from_sources(file.as_deref(), &vars)
+    }
+
+    pub fn from_sources(
+        file: Option<&str>,
+        vars: &HashMap<String, String>,
+    ) -> Result<Settings, Vec<String>> {
+        let file = parse_file(file)?;
+        let defaults = Settings::default();
+        let mut layers = Layers {
+            vars,
+            errors: vec![],
+        };
+
+        let server = ServerSettings {
+            host: layers.pick("HOST", file.server.host, defaults.server.host),
+            port: layers.pick("PORT", file.server.port, defaults.server.port),
+            shutdown_timeout: Duration::from_secs(layers.pick(
+                "SHUTDOWN_TIMEOUT_SECS",
+                file.server.shutdown_timeout_secs,
+                defaults.server.shutdown_timeout.as_secs(),
+            )),
+        };
+        let database =
+            DatabaseSettings::from_layers(&mut layers, file.database, defaults.database);
+        let algorithm = match file.auth.algorithm.map(|algorithm| algorithm.parse()) {
+            Some(Err(())) => {
+                layers.errors.push("auth.algorithm must be HS256 or RS256".to_string());
+                None
+            }
+            Some(Ok(algorithm)) => Some(algorithm),
+            None => None,
+        };
+        let auth = AuthSettings {
+            algorithm: layers.pick("JWT_ALGORITHM", algorithm, defaults.auth.algorithm),
+            secret: layers.pick("JWT_SECRET", file.auth.secret, defaults.auth.secret),
+            private_key: layers.read_file("JWT_PRIVATE_KEY_FILE", file.auth.private_key_file),
+            public_key: layers.read_file("JWT_PUBLIC_KEY_FILE", file.auth.public_key_file),
+            issuer: layers.pick("JWT_ISSUER", file.auth.issuer, defaults.auth.issuer),
+            audience: layers.pick("JWT_AUDIENCE", file.auth.audience, defaults.auth.audience),
+            access_token_ttl: Duration::from_secs(layers.pick(
+                "JWT_ACCESS_TOKEN_TTL_SECS",
+                file.auth.access_token_ttl_secs,
+                defaults.auth.access_token_ttl.as_secs(),
+            )),
+            refresh_token_ttl: Duration::from_secs(layers.pick(
+                "JWT_REFRESH_TOKEN_TTL_SECS",
+                file.auth.refresh_token_ttl_secs,
+                defaults.auth.refresh_token_ttl.as_secs(),
+            )),
+            leeway: Duration::from_secs(layers.pick(
+                "JWT_LEEWAY_SECS",
+                file.auth.leeway_secs,
+                defaults.auth.leeway.as_secs(),
+            )),
+        };
+        let trash = TrashSettings {
+            retention: Duration::from_secs(layers.pick(
+                "TRASH_RETENTION_SECS",
+                file.trash.retention_secs,
+                defaults.trash.retention.as_secs(),
+            )),
+            purge_interval: Duration::from_secs(layers.pick(
+                "TRASH_PURGE_INTERVAL_SECS",
+                file.trash.purge_interval_secs,
+                defaults.trash.purge_interval.as_secs(),
+            )),
+        };
+        let idempotency = IdempotencySettings {
+            ttl: Duration::from_secs(layers.pick(
+                "IDEMPOTENCY_TTL_SECS",
+                file.idempotency.ttl_secs,
+                defaults.idempotency.ttl.as_secs(),
+            )),
+            lock_timeout: Duration::from_secs(layers.pick(
+                "IDEMPOTENCY_LOCK_TIMEOUT_SECS",
+                file.idempotency.lock_timeout_secs,
+                defaults.idempotency.lock_timeout.as_secs(),
+            )),
+        };
+        let bulk = BulkSettings {
+            max_operations: layers.pick(
+                "BULK_MAX_OPERATIONS",
+                file.bulk.max_operations,
+                defaults.bulk.max_operations,
+            ),
+        };
+        let migrate_on_start =
+            layers.pick_bool("MIGRATE_ON_START", file.migrate_on_start, defaults.migrate_on_start);
+        let pagination = match PaginationConfig::from_sources(&file.pagination, vars) {
+            Ok(pagination) => pagination,
+            Err(errors) => {
+                layers.errors.extend(errors);
+                defaults.pagination
+            }
+        };
+
+        let mut errors = layers.errors;
+        database.validate(&mut errors);
+        match auth.algorithm {
+            JwtAlgorithm::Hs256 if auth.secret.len() < MIN_JWT_SECRET_LENGTH => errors.push(format!(
+                "JWT_SECRET must be at least {} characters",
+                MIN_JWT_SECRET_LENGTH
+            )),
+            JwtAlgorithm::Rs256 if auth.private_key.is_empty() || auth.public_key.is_empty() => {
+                errors.push(
+                    "JWT_PRIVATE_KEY_FILE and JWT_PUBLIC_KEY_FILE are required for RS256"
+                        .to_string(),
+                )
+            }
+            _ => {
+                if let Err(error) = JwtKeys::new(&auth) {
+                    errors.push(error);
+                }
+            }
+        }
+        if auth.access_token_ttl.is_zero() {
+            errors.push("JWT_ACCESS_TOKEN_TTL_SECS must be at least 1".to_string());
+        }
+        if auth.refresh_token_ttl <= auth.access_token_ttl {
+            errors.push(
+                "JWT_REFRESH_TOKEN_TTL_SECS must be greater than JWT_ACCESS_TOKEN_TTL_SECS"
+                    .to_string(),
+            );
+        }
+        if trash.purge_interval.is_zero() {
+            errors.push("TRASH_PURGE_INTERVAL_SECS must be at least 1".to_string());
+        }
+        if idempotency.ttl.is_zero() {
+            errors.push("IDEMPOTENCY_TTL_SECS must be at least 1".to_string());
+        }
+        if idempotency.lock_timeout.is_zero() {
+            errors.push("IDEMPOTENCY_LOCK_TIMEOUT_SECS must be at least 1".to_string());
+        }
+        if bulk.max_operations == 0 {
+            errors.push("BULK_MAX_OPERATIONS must be at least 1".to_string());
+        }
+
+        match errors.is_empty() {
+            true => Ok(Settings {
+                server,
+                database,
+                auth,
+                trash,
+                idempotency,
+                bulk,
+                migrate_on_start,
+                pagination,
+            }),
+            false => Err(errors),
+        }
+    }
+}
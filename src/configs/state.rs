@@ -1,17 +1,50 @@
-use std::{env, sync::Arc};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
-use super::db::new_pg_pool;
+use super::{db::new_pg_pool, settings::Settings};
+use crate::utils::jwt::JwtKeys;
 use sqlx::Postgres;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db_pool: sqlx::Pool<Postgres>,
+    pub settings: Settings,
+    pub jwt: JwtKeys,
+    ready: Arc<AtomicBool>,
 }
 
-pub async fn build_state() -> Arc<AppState> {
-    let database_url = env::var("DATABASE_URL")
-        .unwrap_or_else(|error| panic!("Found error on initializing database: {}", error));
-    Arc::new(AppState {
-        db_pool: new_pg_pool(&database_url).await,
-    })
+impl AppState {
+    // Default settings are enough for tests that only need a pool.
+    #[cfg(test)]
+    pub fn new(db_pool: sqlx::Pool<Postgres>) -> Self {
+        AppState::with_settings(db_pool, Settings::default())
+    }
+
+    // Settings are expected to be validated already, see `Settings::from_sources`.
+    pub fn with_settings(db_pool: sqlx::Pool<Postgres>, settings: Settings) -> Self {
+        let jwt = JwtKeys::new(&settings.auth).unwrap_or_else(|error| panic!("{}", error));
+        AppState {
+            db_pool,
+            settings,
+            jwt,
+            ready: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    // Called as soon as shutdown begins so load balancers stop routing new
+    // requests here while in-flight ones drain.
+    pub fn mark_not_ready(&self) {
+        self.ready.store(false, Ordering::SeqCst);
+    }
+}
+
+pub async fn build_state(settings: Settings) -> Arc<AppState> {
+    let db_pool = new_pg_pool(&settings.database).await;
+    Arc::new(AppState::with_settings(db_pool, settings))
 }
@@ -1,15 +1,27 @@
+use std::borrow::Cow;
+
 use axum::{body::Body, http::{Response, StatusCode}, response::IntoResponse, Json};
 use serde::Serialize;
 
 #[derive(Serialize)]
 pub struct DefaultErrorMessage {
     pub code: u16,
-    pub message: &'static str,
+    pub message: Cow<'static, str>,
 }
 
 pub struct JsonErrorMessage(StatusCode, Json<DefaultErrorMessage>);
 
 impl JsonErrorMessage {
+    pub fn new(status_code: StatusCode, message: String) -> Self {
+        JsonErrorMessage(
+            status_code,
+            Json(DefaultErrorMessage {
+                code: status_code.as_u16(),
+                message: Cow::Owned(message),
+            }),
+        )
+    }
+
     pub fn into_response(self) ->  (StatusCode, Response<Body>) {
         (self.0, self.1.into_response())
     }   
@@ -17,15 +29,33 @@ impl JsonErrorMessage {
 
 const INTERNAL_ERROR_MESSAGE: &str = "internal server error";
 const NOT_FOUND_ERROR_MESSAGE: &str = "not found";
+const CONFLICT_ERROR_MESSAGE: &str = "conflict";
+const FORBIDDEN_ERROR_MESSAGE: &str = "forbidden";
+const PRECONDITION_FAILED_ERROR_MESSAGE: &str = "precondition failed";
 
 const GENERIC_INTERNAL_ERROR: DefaultErrorMessage = DefaultErrorMessage {
     code: 500,
-    message: INTERNAL_ERROR_MESSAGE,
+    message: Cow::Borrowed(INTERNAL_ERROR_MESSAGE),
 };
 
 const GENERIC_NOT_FOUND_ERROR: DefaultErrorMessage = DefaultErrorMessage {
     code: 404,
-    message: NOT_FOUND_ERROR_MESSAGE,
+    message: Cow::Borrowed(NOT_FOUND_ERROR_MESSAGE),
+};
+
+const GENERIC_CONFLICT_ERROR: DefaultErrorMessage = DefaultErrorMessage {
+    code: 409,
+    message: Cow::Borrowed(CONFLICT_ERROR_MESSAGE),
+};
+
+const GENERIC_FORBIDDEN_ERROR: DefaultErrorMessage = DefaultErrorMessage {
+    code: 403,
+    message: Cow::Borrowed(FORBIDDEN_ERROR_MESSAGE),
+};
+
+const GENERIC_PRECONDITION_FAILED_ERROR: DefaultErrorMessage = DefaultErrorMessage {
+    code: 412,
+    message: Cow::Borrowed(PRECONDITION_FAILED_ERROR_MESSAGE),
 };
 
 pub const GENERIC_INTERNAL_SERVER_ERROR_RESPONSE: JsonErrorMessage = JsonErrorMessage(
@@ -37,3 +67,18 @@ pub const GENERIC_NOT_FOUND_ERROR_RESPONSE: JsonErrorMessage = JsonErrorMessage(
     StatusCode::NOT_FOUND,
     Json(GENERIC_NOT_FOUND_ERROR),
 );
+
+pub const GENERIC_CONFLICT_ERROR_RESPONSE: JsonErrorMessage = JsonErrorMessage(
+    StatusCode::CONFLICT,
+    Json(GENERIC_CONFLICT_ERROR),
+);
+
+pub const GENERIC_FORBIDDEN_ERROR_RESPONSE: JsonErrorMessage = JsonErrorMessage(
+    StatusCode::FORBIDDEN,
+    Json(GENERIC_FORBIDDEN_ERROR),
+);
+
+pub const GENERIC_PRECONDITION_FAILED_ERROR_RESPONSE: JsonErrorMessage = JsonErrorMessage(
+    StatusCode::PRECONDITION_FAILED,
+    Json(GENERIC_PRECONDITION_FAILED_ERROR),
+);
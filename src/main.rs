@@ -6,23 +6,110 @@ mod utils;
 mod views;
 mod tests;
 
-use std::sync::Arc;
+use std::{env, process};
 
-use axum::Router;
-use configs::state;
+use configs::{
+    migrations,
+    settings::{DatabaseSettings, Settings},
+    state,
+};
 use dotenvy::dotenv;
-use utils::app;
+use modules::{admin::service::AdminService, users::models::normalize_email};
+use utils::{app, shutdown, trash};
 
 #[tokio::main]
 async fn main() {
     dotenv().ok();
-    
-    let mut module_list: Vec<(&str, fn() -> Router<Arc<state::AppState>>)>  = vec![];
-    module_list.push(("/todos", router::todos_router));
 
-    let router = app::build_router(module_list);
+    let args: Vec<String> = env::args().collect();
+    if let Some(command) = args.get(1) {
+        run_command(command, &args[2..]).await;
+        return;
+    }
 
-    let listener = app::build_listener(app::build_listening_address()).await;
-    axum::serve(listener, router.with_state(state::build_state().await)).await.unwrap();
+    let settings = Settings::load().unwrap_or_else(exit_with_errors);
+    let state = state::build_state(settings).await;
+
+    if state.settings.migrate_on_start {
+        migrations::run(&state.db_pool)
+            .await
+            .unwrap_or_else(|error| panic!("{}", migrations::describe_error(error)));
+    } else {
+        migrations::ensure_schema_not_ahead(&state.db_pool)
+            .await
+            .unwrap_or_else(|error| panic!("{}", migrations::describe_error(error)));
+    }
+
+    let router = router::app_router(state.clone());
+    let purge = trash::spawn_purge(state.db_pool.clone(), state.settings.trash.clone());
+
+    let listener =
+        app::build_listener(app::build_listening_address(&state.settings.server)).await;
+    let drain_timeout = state.settings.server.shutdown_timeout;
+    let signal_state = state.clone();
+    let shutdown = async move {
+        shutdown::signal().await;
+        signal_state.mark_not_ready();
+        println!("Shutting down, draining in-flight requests");
+    };
+    let drained = shutdown::serve(listener, router, shutdown, drain_timeout).await.unwrap();
+    if !drained {
+        eprintln!(
+            "Drain timeout of {}s elapsed with requests still in flight",
+            drain_timeout.as_secs()
+        );
+    }
+    purge.abort();
+    state.db_pool.close().await;
 }
 
+fn exit_with_errors<T>(errors: Vec<String>) -> T {
+    eprintln!("Found errors on loading configuration:");
+    for error in errors.iter() {
+        eprintln!("  - {}", error);
+    }
+    process::exit(1);
+}
+
+// Commands only talk to the database, so they load just its settings and
+// keep working when the rest, e.g. the JWT secret of the server, is missing.
+async fn run_command(command: &str, args: &[String]) {
+    let database = DatabaseSettings::load().unwrap_or_else(exit_with_errors);
+    let state = state::build_state(Settings {
+        database,
+        ..Settings::default()
+    })
+    .await;
+
+    match command {
+        "migrate" => {
+            if let Err(error) =
+                migrations::run_command(&state.db_pool, args.first().map(String::as_str)).await
+            {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        }
+        "grant-admin" => {
+            let Some(email) = args.first() else {
+                eprintln!("usage: grant-admin <email>");
+                process::exit(1);
+            };
+            match AdminService::new(state.clone()).grant_admin(&normalize_email(email)).await {
+                Ok(true) => println!("{} is now an administrator", email),
+                Ok(false) => {
+                    eprintln!("no user with email {}", email);
+                    process::exit(1);
+                }
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+            }
+        }
+        command => {
+            eprintln!("unknown command: {}", command);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use super::{service::AdminService, views};
+use crate::configs::state::AppState;
+use crate::modules::users;
+use crate::utils::auth::AuthUser;
+use crate::utils::error::AppError;
+use crate::utils::policy::{self, Permission};
+use crate::utils::query::Query;
+use crate::views::pagination::Pagination;
+use axum::{
+    extract::{rejection::PathRejection, Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct IdPath {
+    id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct ListUsersQuery {
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+pub async fn list_users(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Query(query): Query<ListUsersQuery>,
+) -> Result<(StatusCode, Json<Pagination<users::views::User>>), AppError> {
+    policy::authorize(&auth_user, Permission::ManageUsers)?;
+    let (limit, requested_limit) = state
+        .settings
+        .pagination
+        .for_module("users")
+        .resolve_query(query.limit, query.offset)?;
+    let offset = query.offset.unwrap_or(0);
+
+    let admin_service = AdminService::new(state);
+    let list = admin_service.list_users(limit, offset).await?;
+    let total = admin_service.count_users().await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(Pagination {
+            limit,
+            requested_limit,
+            offset,
+            total: Some(total),
+            items: list.into_iter().map(users::views::User::from).collect(),
+        }),
+    ))
+}
+
+pub async fn disable_user(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    path: Result<Path<IdPath>, PathRejection>,
+) -> Result<(StatusCode, Json<users::views::User>), AppError> {
+    policy::authorize(&auth_user, Permission::ManageUsers)?;
+    let Path(IdPath { id }) = path?;
+    // Locking out the last administrator by accident is not recoverable
+    // through the API.
+    if id == auth_user.id {
+        return Err(AppError::Forbidden);
+    }
+
+    let user = AdminService::new(state).disable_user(id).await?;
+
+    Ok((StatusCode::OK, Json(users::views::User::from(user))))
+}
+
+pub async fn enable_user(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    path: Result<Path<IdPath>, PathRejection>,
+) -> Result<(StatusCode, Json<users::views::User>), AppError> {
+    policy::authorize(&auth_user, Permission::ManageUsers)?;
+    let Path(IdPath { id }) = path?;
+
+    let user = AdminService::new(state).enable_user(id).await?;
+
+    Ok((StatusCode::OK, Json(users::views::User::from(user))))
+}
+
+pub async fn get_todo(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    path: Result<Path<IdPath>, PathRejection>,
+) -> Result<(StatusCode, Json<views::Todo>), AppError> {
+    policy::authorize(&auth_user, Permission::ReadAnyTodo)?;
+    let Path(IdPath { id }) = path?;
+
+    let todo = AdminService::new(state).find_todo(id).await?;
+
+    Ok((StatusCode::OK, Json(views::Todo::from(todo))))
+}
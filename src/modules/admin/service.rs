@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use sqlx::Postgres;
+
+use crate::{
+    configs::state::AppState,
+    modules::{todos, users},
+};
+
+/// Queries that cross user boundaries. Callers must have been authorized
+/// through `utils::policy` before using it.
+pub struct AdminService {
+    db_pool: sqlx::Pool<Postgres>,
+}
+impl AdminService {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self {
+            db_pool: state.db_pool.clone(),
+        }
+    }
+
+    pub async fn list_users(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<users::models::User>, sqlx::Error> {
+        sqlx::query_as::<_, users::models::User>(
+            "SELECT * FROM users ORDER BY id LIMIT $1 OFFSET $2;",
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db_pool)
+        .await
+    }
+
+    pub async fn count_users(&self) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM users;")
+            .fetch_one(&self.db_pool)
+            .await
+    }
+
+    // Disabling also revokes every refresh token of the user so no new
+    // access token can be obtained; existing ones are rejected by the
+    // extractor, which checks `disabled_at` on every request.
+    pub async fn disable_user(&self, id: i32) -> Result<users::models::User, sqlx::Error> {
+        let mut transaction = self.db_pool.begin().await?;
+        let user = sqlx::query_as::<_, users::models::User>(
+            "UPDATE users SET disabled_at = COALESCE(disabled_at, now()), updated_at = now() \
+            WHERE id = $1 RETURNING *;",
+        )
+        .bind(id)
+        .fetch_one(&mut *transaction)
+        .await?;
+        sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = now() WHERE user_id = $1 AND revoked_at IS NULL;",
+        )
+        .bind(id)
+        .execute(&mut *transaction)
+        .await?;
+        transaction.commit().await?;
+        Ok(user)
+    }
+
+    pub async fn enable_user(&self, id: i32) -> Result<users::models::User, sqlx::Error> {
+        sqlx::query_as::<_, users::models::User>(
+            "UPDATE users SET disabled_at = NULL, updated_at = now() WHERE id = $1 RETURNING *;",
+        )
+        .bind(id)
+        .fetch_one(&self.db_pool)
+        .await
+    }
+
+    pub async fn find_todo(&self, id: i32) -> Result<todos::models::Todo, sqlx::Error> {
+        let mut conn = self.db_pool.acquire().await?;
+        let mut todo =
+            sqlx::query_as::<_, todos::models::Todo>("SELECT * FROM todos WHERE id = $1;")
+                .bind(id)
+                .fetch_one(&mut *conn)
+                .await?;
+        todos::service::load_details(&mut conn, std::slice::from_mut(&mut todo)).await?;
+        Ok(todo)
+    }
+
+    // Returns whether a user with this email exists.
+    pub async fn grant_admin(&self, email: &str) -> Result<bool, sqlx::Error> {
+        sqlx::query("UPDATE users SET role = 'admin', updated_at = now() WHERE email = $1;")
+            .bind(email)
+            .execute(&self.db_pool)
+            .await
+            .map(|result| result.rows_affected() == 1)
+    }
+}
@@ -0,0 +1,21 @@
+use serde::Serialize;
+
+use crate::modules::todos;
+
+// Unlike the owner's view, administrators need to know whose todo it is and
+// whether it is in the trash.
+#[derive(Serialize)]
+pub struct Todo {
+    #[serde(flatten)]
+    pub todo: todos::views::TrashedTodo,
+    pub owner_id: Option<i32>,
+}
+
+impl From<todos::models::Todo> for Todo {
+    fn from(todo: todos::models::Todo) -> Self {
+        Todo {
+            owner_id: todo.owner_id,
+            todo: todos::views::TrashedTodo::from(todo),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use super::{models, service::ApiKeyService, views};
+use crate::configs::state::AppState;
+use crate::utils::auth::AuthUser;
+use crate::utils::error::AppError;
+use crate::utils::policy::{self, Permission};
+use crate::utils::token;
+use crate::utils::validation::{is_single_line, Rule, Validate, Validator};
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection},
+        Path, State,
+    },
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+
+// Makes keys recognizable, e.g. by secret scanners, and is part of the key.
+const KEY_PREFIX: &str = "tdk_";
+const DISPLAYED_PREFIX_LENGTH: usize = 12;
+
+#[derive(Deserialize)]
+pub struct ApiKeyPath {
+    id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct CreateApiKeyRequest {
+    name: String,
+    scope: models::ApiKeyScope,
+}
+
+#[derive(Deserialize)]
+pub struct RenameApiKeyRequest {
+    name: String,
+}
+
+const NAME_RULES: &[Rule] = &[
+    Rule::Required,
+    Rule::NotBlank,
+    Rule::MaxLength(100),
+    Rule::AllowedCharacters(is_single_line, "printable characters"),
+];
+
+impl Validate for CreateApiKeyRequest {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .field("name", Some(&self.name), NAME_RULES)
+            .finish()
+    }
+}
+
+impl Validate for RenameApiKeyRequest {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .field("name", Some(&self.name), NAME_RULES)
+            .finish()
+    }
+}
+
+pub async fn post(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    request: Result<Json<CreateApiKeyRequest>, JsonRejection>,
+) -> Result<(StatusCode, Json<views::CreatedApiKey>), AppError> {
+    policy::authorize(&auth_user, Permission::ManageApiKeys)?;
+    let Json(request) = request?;
+    request.validate()?;
+
+    let key = format!("{}{}", KEY_PREFIX, token::generate());
+    let api_key_service = ApiKeyService::new(state, auth_user.id);
+    let api_key = api_key_service
+        .create(&models::ApiKeyFields {
+            name: request.name,
+            prefix: key[..DISPLAYED_PREFIX_LENGTH].to_string(),
+            key_hash: token::hash(&key),
+            scope: request.scope,
+        })
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(views::CreatedApiKey {
+            api_key: views::ApiKey::from(api_key),
+            key,
+        }),
+    ))
+}
+
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+) -> Result<(StatusCode, Json<Vec<views::ApiKey>>), AppError> {
+    policy::authorize(&auth_user, Permission::ManageApiKeys)?;
+
+    let api_key_service = ApiKeyService::new(state, auth_user.id);
+    let api_keys = api_key_service.list().await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(api_keys.into_iter().map(views::ApiKey::from).collect()),
+    ))
+}
+
+pub async fn patch(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    path: Result<Path<ApiKeyPath>, PathRejection>,
+    request: Result<Json<RenameApiKeyRequest>, JsonRejection>,
+) -> Result<(StatusCode, Json<views::ApiKey>), AppError> {
+    policy::authorize(&auth_user, Permission::ManageApiKeys)?;
+    let Path(ApiKeyPath { id }) = path?;
+    let Json(request) = request?;
+    request.validate()?;
+
+    let api_key_service = ApiKeyService::new(state, auth_user.id);
+    let api_key = api_key_service.rename(id, &request.name).await?;
+
+    Ok((StatusCode::OK, Json(views::ApiKey::from(api_key))))
+}
+
+pub async fn delete(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    path: Result<Path<ApiKeyPath>, PathRejection>,
+) -> Result<StatusCode, AppError> {
+    policy::authorize(&auth_user, Permission::ManageApiKeys)?;
+    let Path(ApiKeyPath { id }) = path?;
+
+    let api_key_service = ApiKeyService::new(state, auth_user.id);
+    api_key_service.revoke(id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
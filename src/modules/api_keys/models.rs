@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "api_key_scope", rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    ReadOnly,
+    ReadWrite,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ApiKey {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub prefix: String,
+    pub scope: ApiKeyScope,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct ApiKeyFields {
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub scope: ApiKeyScope,
+}
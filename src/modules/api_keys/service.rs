@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use sqlx::Postgres;
+
+use crate::configs::state::AppState;
+
+use super::models;
+
+/// Manages the API keys of a single user.
+pub struct ApiKeyService {
+    db_pool: sqlx::Pool<Postgres>,
+    user_id: i32,
+}
+impl ApiKeyService {
+    pub fn new(state: Arc<AppState>, user_id: i32) -> Self {
+        Self {
+            db_pool: state.db_pool.clone(),
+            user_id,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        fields: &models::ApiKeyFields,
+    ) -> Result<models::ApiKey, sqlx::Error> {
+        sqlx::query_as::<_, models::ApiKey>(
+            "INSERT INTO api_keys (user_id, name, prefix, key_hash, scope) \
+            VALUES ($1, $2, $3, $4, $5) RETURNING *;",
+        )
+        .bind(self.user_id)
+        .bind(&fields.name)
+        .bind(&fields.prefix)
+        .bind(&fields.key_hash)
+        .bind(fields.scope)
+        .fetch_one(&self.db_pool)
+        .await
+    }
+
+    pub async fn list(&self) -> Result<Vec<models::ApiKey>, sqlx::Error> {
+        sqlx::query_as::<_, models::ApiKey>("SELECT * FROM api_keys WHERE user_id = $1 ORDER BY id;")
+            .bind(self.user_id)
+            .fetch_all(&self.db_pool)
+            .await
+    }
+
+    pub async fn rename(&self, id: i32, name: &str) -> Result<models::ApiKey, sqlx::Error> {
+        sqlx::query_as::<_, models::ApiKey>(
+            "UPDATE api_keys SET name = $1 WHERE id = $2 AND user_id = $3 RETURNING *;",
+        )
+        .bind(name)
+        .bind(id)
+        .bind(self.user_id)
+        .fetch_one(&self.db_pool)
+        .await
+    }
+
+    // Revoking is idempotent: an already revoked key keeps its original timestamp.
+    pub async fn revoke(&self, id: i32) -> Result<models::ApiKey, sqlx::Error> {
+        sqlx::query_as::<_, models::ApiKey>(
+            "UPDATE api_keys SET revoked_at = COALESCE(revoked_at, now()) \
+            WHERE id = $1 AND user_id = $2 RETURNING *;",
+        )
+        .bind(id)
+        .bind(self.user_id)
+        .fetch_one(&self.db_pool)
+        .await
+    }
+}
+
+/// Looks up the active key behind `key_hash` and records that it was used.
+pub async fn authenticate(
+    db_pool: &sqlx::Pool<Postgres>,
+    key_hash: &str,
+) -> Result<models::ApiKey, sqlx::Error> {
+    sqlx::query_as::<_, models::ApiKey>(
+        "UPDATE api_keys SET last_used_at = now() \
+        WHERE key_hash = $1 AND revoked_at IS NULL RETURNING *;",
+    )
+    .bind(key_hash)
+    .fetch_one(db_pool)
+    .await
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::models;
+
+#[derive(Serialize)]
+pub struct ApiKey {
+    pub id: i32,
+    pub name: String,
+    pub prefix: String,
+    pub scope: models::ApiKeyScope,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<models::ApiKey> for ApiKey {
+    fn from(api_key: models::ApiKey) -> Self {
+        ApiKey {
+            id: api_key.id,
+            name: api_key.name,
+            prefix: api_key.prefix,
+            scope: api_key.scope,
+            last_used_at: api_key.last_used_at,
+            revoked_at: api_key.revoked_at,
+            created_at: api_key.created_at,
+        }
+    }
+}
+
+// The plain key is only ever returned once, right after it was created.
+#[derive(Serialize)]
+pub struct CreatedApiKey {
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    pub key: String,
+}
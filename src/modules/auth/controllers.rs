@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use super::{models, service::AuthService, views};
+use crate::configs::state::AppState;
+use crate::utils::auth::authenticate;
+use crate::utils::error::AppError;
+use crate::utils::token;
+use crate::views::errors::UnauthorizedErrorMessage;
+use axum::{
+    extract::{rejection::JsonRejection, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct LoginRequest {
+    email: String,
+    password: String,
+}
+
+#[derive(Deserialize)]
+pub struct RefreshRequest {
+    refresh_token: String,
+}
+
+fn refresh_token_expiry(state: &AppState) -> DateTime<Utc> {
+    Utc::now() + state.settings.auth.refresh_token_ttl
+}
+
+fn token_pair(
+    state: &AppState,
+    user_id: i32,
+    refresh_token: String,
+) -> Result<views::TokenPair, AppError> {
+    Ok(views::TokenPair {
+        access_token: state.jwt.issue(user_id).map_err(AppError::Internal)?,
+        token_type: "Bearer",
+        expires_in: state.jwt.access_token_ttl(),
+        refresh_token,
+    })
+}
+
+pub async fn login(
+    State(state): State<Arc<AppState>>,
+    request: Result<Json<LoginRequest>, JsonRejection>,
+) -> Result<(StatusCode, Json<views::TokenPair>), AppError> {
+    let Json(request) = request?;
+    let user = authenticate(state.clone(), &request.email, request.password).await?;
+
+    let refresh_token = token::generate();
+    let auth_service = AuthService::new(state.clone());
+    auth_service
+        .create(&models::RefreshTokenFields {
+            user_id: user.id,
+            family_id: Uuid::new_v4(),
+            token_hash: token::hash(&refresh_token),
+            expires_at: refresh_token_expiry(&state),
+        })
+        .await?;
+
+    Ok((StatusCode::OK, Json(token_pair(&state, user.id, refresh_token)?)))
+}
+
+pub async fn refresh(
+    State(state): State<Arc<AppState>>,
+    request: Result<Json<RefreshRequest>, JsonRejection>,
+) -> Result<(StatusCode, Json<views::TokenPair>), AppError> {
+    let Json(request) = request?;
+
+    let refresh_token = token::generate();
+    let auth_service = AuthService::new(state.clone());
+    let rotation = auth_service
+        .rotate(
+            &token::hash(&request.refresh_token),
+            &token::hash(&refresh_token),
+            refresh_token_expiry(&state),
+        )
+        .await?;
+
+    match rotation {
+        models::Rotation::Rotated(rotated) => Ok((
+            StatusCode::OK,
+            Json(token_pair(&state, rotated.user_id, refresh_token)?),
+        )),
+        models::Rotation::Rejected => Err(AppError::Unauthorized(
+            UnauthorizedErrorMessage::invalid_refresh_token(),
+        )),
+        models::Rotation::Disabled => Err(AppError::Unauthorized(
+            UnauthorizedErrorMessage::account_disabled(),
+        )),
+    }
+}
+
+pub async fn logout(
+    State(state): State<Arc<AppState>>,
+    request: Result<Json<RefreshRequest>, JsonRejection>,
+) -> Result<StatusCode, AppError> {
+    let Json(request) = request?;
+
+    let auth_service = AuthService::new(state);
+    auth_service
+        .revoke_family(&token::hash(&request.refresh_token))
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
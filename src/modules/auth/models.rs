@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct RefreshToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub family_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+pub struct RefreshTokenFields {
+    pub user_id: i32,
+    pub family_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub enum Rotation {
+    Rotated(RefreshToken),
+    // The token is unknown, expired or was already rotated or revoked.
+    Rejected,
+    // The token is valid but belongs to a disabled user.
+    Disabled,
+}
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sqlx::Postgres;
+
+use crate::configs::state::AppState;
+
+use super::models;
+
+pub struct AuthService {
+    db_pool: sqlx::Pool<Postgres>,
+}
+impl AuthService {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self {
+            db_pool: state.db_pool.clone(),
+        }
+    }
+
+    pub async fn create(
+        &self,
+        fields: &models::RefreshTokenFields,
+    ) -> Result<models::RefreshToken, sqlx::Error> {
+        sqlx::query_as::<_, models::RefreshToken>(
+            "INSERT INTO refresh_tokens (user_id, family_id, token_hash, expires_at) \
+            VALUES ($1, $2, $3, $4) RETURNING *;",
+        )
+        .bind(fields.user_id)
+        .bind(fields.family_id)
+        .bind(&fields.token_hash)
+        .bind(fields.expires_at)
+        .fetch_one(&self.db_pool)
+        .await
+    }
+
+    // Revokes the token behind `token_hash` and replaces it with a new token of
+    // the same family. Presenting an already revoked token means it leaked, so
+    // the whole family is revoked. Disabling a user revokes their tokens, but
+    // one issued by a login racing `AdminService::disable_user` is rejected
+    // here as well.
+    pub async fn rotate(
+        &self,
+        token_hash: &str,
+        new_token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<models::Rotation, sqlx::Error> {
+        let mut transaction = self.db_pool.begin().await?;
+        let current = sqlx::query_as::<_, models::RefreshToken>(
+            "SELECT * FROM refresh_tokens WHERE token_hash = $1 FOR UPDATE;",
+        )
+        .bind(token_hash)
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        let current = match current {
+            None => return Ok(models::Rotation::Rejected),
+            Some(current) if current.revoked_at.is_some() => {
+                sqlx::query(
+                    "UPDATE refresh_tokens SET revoked_at = now() \
+                    WHERE family_id = $1 AND revoked_at IS NULL;",
+                )
+                .bind(current.family_id)
+                .execute(&mut *transaction)
+                .await?;
+                transaction.commit().await?;
+                return Ok(models::Rotation::Rejected);
+            }
+            Some(current) if current.expires_at <= Utc::now() => {
+                return Ok(models::Rotation::Rejected)
+            }
+            Some(current) => current,
+        };
+        let is_disabled = sqlx::query_scalar::<_, bool>(
+            "SELECT disabled_at IS NOT NULL FROM users WHERE id = $1;",
+        )
+        .bind(current.user_id)
+        .fetch_one(&mut *transaction)
+        .await?;
+        if is_disabled {
+            return Ok(models::Rotation::Disabled);
+        }
+
+        sqlx::query("UPDATE refresh_tokens SET revoked_at = now() WHERE id = $1;")
+            .bind(current.id)
+            .execute(&mut *transaction)
+            .await?;
+        let rotated = sqlx::query_as::<_, models::RefreshToken>(
+            "INSERT INTO refresh_tokens (user_id, family_id, token_hash, expires_at) \
+            VALUES ($1, $2, $3, $4) RETURNING *;",
+        )
+        .bind(current.user_id)
+        .bind(current.family_id)
+        .bind(new_token_hash)
+        .bind(expires_at)
+        .fetch_one(&mut *transaction)
+        .await?;
+        transaction.commit().await?;
+
+        Ok(models::Rotation::Rotated(rotated))
+    }
+
+    pub async fn revoke_family(&self, token_hash: &str) -> Result<u64, sqlx::Error> {
+        sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = now() WHERE revoked_at IS NULL \
+            AND family_id = (SELECT family_id FROM refresh_tokens WHERE token_hash = $1);",
+        )
+        .bind(token_hash)
+        .execute(&self.db_pool)
+        .await
+        .map(|result| result.rows_affected())
+    }
+}
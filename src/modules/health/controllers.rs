@@ -0,0 +1,56 @@
+use std::{collections::BTreeMap, future::Future, sync::Arc, time::Instant};
+
+use super::{service::HealthService, views};
+use crate::configs::state::AppState;
+use axum::{extract::State, http::StatusCode, Json};
+
+async fn timed<F>(check: F) -> views::Check
+where
+    F: Future<Output = Result<(), String>>,
+{
+    let started = Instant::now();
+    let result = check.await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    match result {
+        Ok(()) => views::Check {
+            status: views::Status::Ok,
+            latency_ms,
+            error: None,
+        },
+        Err(error) => views::Check {
+            status: views::Status::Unavailable,
+            latency_ms,
+            error: Some(error),
+        },
+    }
+}
+
+pub async fn liveness() -> (StatusCode, Json<views::Health>) {
+    (StatusCode::OK, Json(views::Health::from_checks(BTreeMap::new())))
+}
+
+pub async fn readiness(State(state): State<Arc<AppState>>) -> (StatusCode, Json<views::Health>) {
+    let shutting_down = !state.is_ready();
+    let health_service = HealthService::new(state);
+
+    let mut checks = BTreeMap::new();
+    checks.insert("database", timed(health_service.check_database()).await);
+    checks.insert("migrations", timed(health_service.check_migrations()).await);
+    if shutting_down {
+        checks.insert(
+            "shutdown",
+            views::Check {
+                status: views::Status::Unavailable,
+                latency_ms: 0,
+                error: Some("server is shutting down".to_string()),
+            },
+        );
+    }
+
+    let health = views::Health::from_checks(checks);
+    let status = match health.status {
+        views::Status::Ok => StatusCode::OK,
+        views::Status::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    (status, Json(health))
+}
@@ -0,0 +1,3 @@
+pub mod controllers;
+pub mod views;
+pub mod service;
\ No newline at end of file
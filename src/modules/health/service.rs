@@ -0,0 +1,64 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use sqlx::Postgres;
+
+use crate::configs::{migrations, state::AppState};
+
+// Readiness probes are polled often; don't let them hang on the pool's
+// (much longer) acquire timeout.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct HealthService {
+    db_pool: sqlx::Pool<Postgres>,
+}
+impl HealthService {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self {
+            db_pool: state.db_pool.clone(),
+        }
+    }
+
+    // The probe is unauthenticated, so the details of a failure, which may
+    // name hosts and roles, only go to the log.
+    pub async fn check_database(&self) -> Result<(), String> {
+        let query = sqlx::query_scalar::<_, i32>("SELECT 1;").fetch_one(&self.db_pool);
+        match tokio::time::timeout(CHECK_TIMEOUT, query).await {
+            Err(_) => Err("timed out".to_string()),
+            Ok(Err(error)) => Err(unavailable(error)),
+            Ok(Ok(_)) => Ok(()),
+        }
+    }
+
+    pub async fn check_migrations(&self) -> Result<(), String> {
+        let applied = migrations::applied_versions(&self.db_pool);
+        let applied = match tokio::time::timeout(CHECK_TIMEOUT, applied).await {
+            Err(_) => return Err("timed out".to_string()),
+            Ok(result) => result.map_err(unavailable)?,
+        };
+        let applied: HashSet<i64> = applied.into_iter().collect();
+        let known: HashSet<i64> = migrations::MIGRATOR
+            .iter()
+            .map(|migration| migration.version)
+            .collect();
+
+        let mut pending: Vec<i64> = known.difference(&applied).copied().collect();
+        pending.sort_unstable();
+        if let Some(version) = pending.first() {
+            return Err(format!("migration {} is pending", version));
+        }
+        let mut unknown: Vec<i64> = applied.difference(&known).copied().collect();
+        unknown.sort_unstable();
+        match unknown.first() {
+            Some(version) => Err(format!(
+                "migration {} is applied but unknown to this build",
+                version
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+fn unavailable(error: impl std::fmt::Display) -> String {
+    eprintln!("Health check failed {}", error);
+    "unavailable".to_string()
+}
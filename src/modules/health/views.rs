@@ -0,0 +1,35 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    Unavailable,
+}
+
+#[derive(Serialize)]
+pub struct Check {
+    pub status: Status,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct Health {
+    pub status: Status,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub checks: BTreeMap<&'static str, Check>,
+}
+
+impl Health {
+    pub fn from_checks(checks: BTreeMap<&'static str, Check>) -> Self {
+        let status = match checks.values().all(|check| check.status == Status::Ok) {
+            true => Status::Ok,
+            false => Status::Unavailable,
+        };
+        Health { status, checks }
+    }
+}
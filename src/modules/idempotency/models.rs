@@ -0,0 +1,15 @@
+#[derive(Debug, sqlx::FromRow)]
+pub struct IdempotencyKey {
+    pub fingerprint: String,
+    pub status_code: Option<i16>,
+    // Stored as `name: value` lines.
+    pub headers: Vec<String>,
+    pub body: Option<Vec<u8>>,
+}
+
+#[derive(Debug)]
+pub struct StoredResponse {
+    pub status_code: i16,
+    pub headers: Vec<String>,
+    pub body: Vec<u8>,
+}
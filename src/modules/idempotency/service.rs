@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sqlx::Postgres;
+
+use crate::configs::state::AppState;
+
+use super::models;
+
+/// Stores the responses to a single user's requests by idempotency key.
+pub struct IdempotencyService {
+    db_pool: sqlx::Pool<Postgres>,
+    user_id: i32,
+}
+
+impl IdempotencyService {
+    pub fn new(state: Arc<AppState>, user_id: i32) -> Self {
+        Self {
+            db_pool: state.db_pool.clone(),
+            user_id,
+        }
+    }
+
+    /// Claims `key` for a request with the given fingerprint. Returns false
+    /// when the key is already taken, by a finished or an in-flight request.
+    /// A request still unfinished since before `abandoned_before` is taken to
+    /// have died, and its key is claimed over.
+    pub async fn claim(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        abandoned_before: DateTime<Utc>,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO idempotency_keys (user_id, key, fingerprint) VALUES ($1, $2, $3) \
+            ON CONFLICT (user_id, key) DO UPDATE \
+            SET fingerprint = EXCLUDED.fingerprint, created_at = now() \
+            WHERE idempotency_keys.status_code IS NULL AND idempotency_keys.created_at < $4;",
+        )
+        .bind(self.user_id)
+        .bind(key)
+        .bind(fingerprint)
+        .bind(abandoned_before)
+        .execute(&self.db_pool)
+        .await
+        .map(|result| result.rows_affected() == 1)
+    }
+
+    pub async fn find(&self, key: &str) -> Result<models::IdempotencyKey, sqlx::Error> {
+        sqlx::query_as::<_, models::IdempotencyKey>(
+            "SELECT fingerprint, status_code, headers, body FROM idempotency_keys \
+            WHERE user_id = $1 AND key = $2;",
+        )
+        .bind(self.user_id)
+        .bind(key)
+        .fetch_one(&self.db_pool)
+        .await
+    }
+
+    pub async fn complete(
+        &self,
+        key: &str,
+        response: &models::StoredResponse,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE idempotency_keys SET status_code = $1, headers = $2, body = $3 \
+            WHERE user_id = $4 AND key = $5;",
+        )
+        .bind(response.status_code)
+        .bind(&response.headers)
+        .bind(&response.body)
+        .bind(self.user_id)
+        .bind(key)
+        .execute(&self.db_pool)
+        .await
+        .map(|_| ())
+    }
+
+    // Releases a key whose request failed, so that a retry runs again.
+    pub async fn release(&self, key: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE user_id = $1 AND key = $2;")
+            .bind(self.user_id)
+            .bind(key)
+            .execute(&self.db_pool)
+            .await
+            .map(|_| ())
+    }
+
+    // Expired keys are dropped lazily, whenever the same user sends another
+    // idempotent request, which keeps the table bounded without a purge task.
+    pub async fn delete_expired(&self, created_before: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE user_id = $1 AND created_at < $2;")
+            .bind(self.user_id)
+            .bind(created_before)
+            .execute(&self.db_pool)
+            .await
+            .map(|result| result.rows_affected())
+    }
+}
@@ -0,0 +1,230 @@
+use std::sync::Arc;
+
+use super::{models, service::ListService, views};
+use crate::configs::state::AppState;
+use crate::utils::auth::AuthUser;
+use crate::utils::error::AppError;
+use crate::utils::patch::Patch;
+use crate::utils::policy::{self, Permission};
+use crate::utils::query::Query;
+use crate::utils::unit_of_work;
+use crate::utils::validation::{is_hex_color, is_single_line, Rule, Validate, Validator};
+use crate::views::errors::BadRequestErrorMessage;
+use crate::views::pagination::Pagination;
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection},
+        Path, State,
+    },
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct ListPath {
+    id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct ListRequest {
+    name: String,
+    color: Option<String>,
+    #[serde(default)]
+    archived: bool,
+}
+
+impl From<ListRequest> for models::ListFields {
+    fn from(request: ListRequest) -> Self {
+        models::ListFields {
+            name: request.name,
+            color: request.color.map(|color| color.to_lowercase()),
+            archived: request.archived,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ListPatchRequest {
+    #[serde(default)]
+    name: Patch<String>,
+    #[serde(default)]
+    color: Patch<String>,
+    #[serde(default)]
+    archived: Patch<bool>,
+}
+
+impl From<ListPatchRequest> for models::ListChanges {
+    fn from(request: ListPatchRequest) -> Self {
+        models::ListChanges {
+            name: request.name.into_value(),
+            color: request
+                .color
+                .into_option()
+                .map(|color| color.map(|color| color.to_lowercase())),
+            archived: request.archived.into_value(),
+        }
+    }
+}
+
+const NAME_RULES: &[Rule] = &[
+    Rule::Required,
+    Rule::NotBlank,
+    Rule::MaxLength(255),
+    Rule::AllowedCharacters(is_single_line, "printable characters"),
+];
+
+const COLOR_RULES: &[Rule] = &[Rule::Format(is_hex_color, "a hex color like #1e90ff")];
+
+impl Validate for ListRequest {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .field("name", Some(&self.name), NAME_RULES)
+            .field("color", self.color.as_deref(), COLOR_RULES)
+            .finish()
+    }
+}
+
+impl Validate for ListPatchRequest {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .not_null("name", &self.name)
+            .patch_field("name", &self.name, NAME_RULES)
+            .patch_field("color", &self.color, COLOR_RULES)
+            .not_null("archived", &self.archived)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ListQuery {
+    limit: Option<i64>,
+    offset: Option<i64>,
+    archived: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteQuery {
+    reassign_to: Option<i32>,
+}
+
+pub async fn get(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    path: Result<Path<ListPath>, PathRejection>,
+) -> Result<(StatusCode, Json<views::List>), AppError> {
+    policy::authorize(&auth_user, Permission::ReadOwnTodos)?;
+    let Path(ListPath { id }) = path?;
+
+    let list = ListService::new(auth_user.id).find(&state.db_pool, id).await?;
+
+    Ok((StatusCode::OK, Json(views::List::from(list))))
+}
+
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Query(query): Query<ListQuery>,
+) -> Result<(StatusCode, Json<Pagination<views::List>>), AppError> {
+    policy::authorize(&auth_user, Permission::ReadOwnTodos)?;
+    let (limit, requested_limit) = state
+        .settings
+        .pagination
+        .for_module("lists")
+        .resolve_query(query.limit, query.offset)?;
+    let offset = query.offset.unwrap_or(0);
+    let filter = models::ListFilter {
+        archived: query.archived,
+    };
+
+    let lists = ListService::new(auth_user.id)
+        .list(&state.db_pool, &filter, limit, offset)
+        .await?;
+    let total = lists.first().map_or(0, |list| list.total);
+
+    Ok((
+        StatusCode::OK,
+        Json(Pagination {
+            limit,
+            requested_limit,
+            offset,
+            total: Some(total),
+            items: lists.into_iter().map(views::List::from).collect(),
+        }),
+    ))
+}
+
+pub async fn post(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    request: Result<Json<ListRequest>, JsonRejection>,
+) -> Result<(StatusCode, Json<views::List>), AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Json(request) = request?;
+    request.validate()?;
+
+    let list = ListService::new(auth_user.id)
+        .create(&state.db_pool, &request.into())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(views::List::from(list))))
+}
+
+pub async fn patch(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    path: Result<Path<ListPath>, PathRejection>,
+    request: Result<Json<ListPatchRequest>, JsonRejection>,
+) -> Result<(StatusCode, Json<views::List>), AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Path(ListPath { id }) = path?;
+    let Json(request) = request?;
+    request.validate()?;
+
+    let list = ListService::new(auth_user.id)
+        .patch(&state.db_pool, id, &request.into())
+        .await?;
+
+    Ok((StatusCode::OK, Json(views::List::from(list))))
+}
+
+pub async fn delete(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    path: Result<Path<ListPath>, PathRejection>,
+    Query(query): Query<DeleteQuery>,
+) -> Result<StatusCode, AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Path(ListPath { id }) = path?;
+
+    let list_service = ListService::new(auth_user.id);
+    let deleted = unit_of_work::run(&state.db_pool, |transaction| {
+        Box::pin(async move {
+            let list = list_service.find(&mut **transaction, id).await?;
+            if let Some(reassign_to) = query.reassign_to {
+                let is_other_list = reassign_to != list.id
+                    && match list_service.lock(&mut **transaction, reassign_to).await {
+                        Err(sqlx::Error::RowNotFound) => false,
+                        result => result.map(|_| true)?,
+                    };
+                if !is_other_list {
+                    return Err(AppError::BadRequest(BadRequestErrorMessage::invalid_query(
+                        "reassign_to".to_string(),
+                        "must be the id of another one of your lists".to_string(),
+                    )));
+                }
+            }
+            Ok(list_service
+                .delete(&mut **transaction, list.id, query.reassign_to)
+                .await?)
+        })
+    })
+    .await?;
+    if deleted != 1_u64 {
+        return Err(AppError::Internal(format!(
+            "expected to delete 1 list, deleted {}",
+            deleted
+        )));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
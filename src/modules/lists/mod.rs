@@ -0,0 +1,4 @@
+pub mod controllers;
+pub mod views;
+pub mod models;
+pub mod service;
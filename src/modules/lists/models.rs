@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct List {
+    pub id: i32,
+    pub name: String,
+    pub color: Option<String>,
+    pub archived: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[sqlx(default)]
+    pub open_count: i64,
+    #[sqlx(default)]
+    pub completed_count: i64,
+    #[sqlx(default)]
+    pub total: i64,
+}
+
+#[derive(Debug)]
+pub struct ListFields {
+    pub name: String,
+    pub color: Option<String>,
+    pub archived: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct ListChanges {
+    pub name: Option<String>,
+    pub color: Option<Option<String>>,
+    pub archived: Option<bool>,
+}
+
+impl ListChanges {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.color.is_none() && self.archived.is_none()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ListFilter {
+    pub archived: Option<bool>,
+}
@@ -0,0 +1,207 @@
+use std::future::Future;
+
+use sqlx::{Acquire, Postgres, QueryBuilder};
+
+use super::models;
+
+// The per-list counts are computed on read so they can never drift from the
+// todos they summarize.
+const SELECT_WITH_COUNTS: &str = "SELECT lists.*, \
+    COUNT(todos.id) FILTER (WHERE NOT todos.completed) AS open_count, \
+    COUNT(todos.id) FILTER (WHERE todos.completed) AS completed_count, \
+    COUNT(*) OVER () AS total \
+    FROM lists LEFT JOIN todos ON todos.list_id = lists.id AND todos.deleted_at IS NULL \
+    WHERE lists.owner_id = ";
+
+/// Reads and writes the lists owned by a single user.
+///
+/// Like `TodoService`, every method runs on the connection it is given, so
+/// that a handler can combine it with todo changes in one unit of work.
+pub struct ListService {
+    owner_id: i32,
+}
+impl ListService {
+    pub fn new(owner_id: i32) -> Self {
+        Self { owner_id }
+    }
+
+    pub fn find<'a, 'c, A>(
+        &'a self,
+        db: A,
+        id: i32,
+    ) -> impl Future<Output = Result<models::List, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut query = QueryBuilder::<Postgres>::new(SELECT_WITH_COUNTS);
+            query
+                .push_bind(self.owner_id)
+                .push(" AND lists.id = ")
+                .push_bind(id)
+                .push(" GROUP BY lists.id;");
+
+            let mut conn = db.acquire().await?;
+            query
+                .build_query_as::<models::List>()
+                .fetch_one(&mut *conn)
+                .await
+        }
+    }
+
+    // Like `find` without the counts, but also keeps the list from being
+    // deleted until the end of the transaction, so that todos can be attached
+    // to it safely.
+    pub fn lock<'a, 'c, A>(
+        &'a self,
+        db: A,
+        id: i32,
+    ) -> impl Future<Output = Result<(), sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            sqlx::query("SELECT id FROM lists WHERE id = $1 AND owner_id = $2 FOR KEY SHARE;")
+                .bind(id)
+                .bind(self.owner_id)
+                .fetch_one(&mut *conn)
+                .await?;
+            Ok(())
+        }
+    }
+
+    pub fn list<'a, 'c, A>(
+        &'a self,
+        db: A,
+        filter: &'a models::ListFilter,
+        limit: i64,
+        offset: i64,
+    ) -> impl Future<Output = Result<Vec<models::List>, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut query = QueryBuilder::<Postgres>::new(SELECT_WITH_COUNTS);
+            query.push_bind(self.owner_id);
+            if let Some(archived) = filter.archived {
+                query.push(" AND lists.archived = ").push_bind(archived);
+            }
+            query
+                .push(" GROUP BY lists.id ORDER BY lists.id LIMIT ")
+                .push_bind(limit)
+                .push(" OFFSET ")
+                .push_bind(offset)
+                .push(";");
+
+            let mut conn = db.acquire().await?;
+            query
+                .build_query_as::<models::List>()
+                .fetch_all(&mut *conn)
+                .await
+        }
+    }
+
+    pub fn create<'a, 'c, A>(
+        &'a self,
+        db: A,
+        fields: &'a models::ListFields,
+    ) -> impl Future<Output = Result<models::List, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            sqlx::query_as::<_, models::List>(
+                "INSERT INTO lists (owner_id, name, color, archived) VALUES ($1, $2, $3, $4) RETURNING *;",
+            )
+            .bind(self.owner_id)
+            .bind(&fields.name)
+            .bind(&fields.color)
+            .bind(fields.archived)
+            .fetch_one(&mut *conn)
+            .await
+        }
+    }
+
+    pub fn patch<'a, 'c, A>(
+        &'a self,
+        db: A,
+        id: i32,
+        changes: &'a models::ListChanges,
+    ) -> impl Future<Output = Result<models::List, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            if !changes.is_empty() {
+                let mut query =
+                    QueryBuilder::<Postgres>::new("UPDATE lists SET updated_at = now()");
+                if let Some(name) = &changes.name {
+                    query.push(", name = ").push_bind(name);
+                }
+                if let Some(color) = &changes.color {
+                    query.push(", color = ").push_bind(color);
+                }
+                if let Some(archived) = changes.archived {
+                    query.push(", archived = ").push_bind(archived);
+                }
+                query
+                    .push(" WHERE id = ")
+                    .push_bind(id)
+                    .push(" AND owner_id = ")
+                    .push_bind(self.owner_id)
+                    .push(";");
+                query.build().execute(&mut *conn).await?;
+            }
+            self.find(&mut *conn, id).await
+        }
+    }
+
+    // Todos of the deleted list go to the trash, unless `reassign_to` names
+    // another list of the same owner to move them to. Either way they are
+    // detached first, so that the cascade of the foreign key never hits them.
+    pub fn delete<'a, 'c, A>(
+        &'a self,
+        db: A,
+        id: i32,
+        reassign_to: Option<i32>,
+    ) -> impl Future<Output = Result<u64, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            // A savepoint when `db` is a transaction already.
+            let mut transaction = db.begin().await?;
+            if let Some(reassign_to) = reassign_to {
+                sqlx::query(
+                    "UPDATE todos SET list_id = $1, version = version + 1, updated_at = now() \
+                    WHERE list_id = $2 AND owner_id = $3 \
+                    AND EXISTS (SELECT 1 FROM lists WHERE id = $1 AND owner_id = $3);",
+                )
+                .bind(reassign_to)
+                .bind(id)
+                .bind(self.owner_id)
+                .execute(&mut *transaction)
+                .await?;
+            }
+            sqlx::query(
+                "UPDATE todos SET deleted_at = COALESCE(deleted_at, now()), list_id = NULL \
+                WHERE list_id = $1 AND owner_id = $2;",
+            )
+            .bind(id)
+            .bind(self.owner_id)
+            .execute(&mut *transaction)
+            .await?;
+            let deleted = sqlx::query("DELETE FROM lists WHERE id = $1 AND owner_id = $2;")
+                .bind(id)
+                .bind(self.owner_id)
+                .execute(&mut *transaction)
+                .await?
+                .rows_affected();
+            transaction.commit().await?;
+            Ok(deleted)
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::models;
+
+#[derive(Serialize)]
+pub struct List {
+    pub id: i32,
+    pub name: String,
+    pub color: Option<String>,
+    pub archived: bool,
+    pub open_count: i64,
+    pub completed_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<models::List> for List {
+    fn from(list: models::List) -> Self {
+        List {
+            id: list.id,
+            name: list.name,
+            color: list.color,
+            archived: list.archived,
+            open_count: list.open_count,
+            completed_count: list.completed_count,
+            created_at: list.created_at,
+            updated_at: list.updated_at,
+        }
+    }
+}
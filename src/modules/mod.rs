@@ -1 +1,10 @@
-pub mod todos;
\ No newline at end of file
+pub mod admin;
+pub mod api_keys;
+pub mod auth;
+pub mod health;
+pub mod idempotency;
+pub mod lists;
+pub mod tags;
+pub mod todo_items;
+pub mod todos;
+pub mod users;
\ No newline at end of file
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use super::{models, service::TagService, views};
+use crate::configs::state::AppState;
+use crate::modules::todos::{self, service::TodoService};
+use crate::utils::auth::AuthUser;
+use crate::utils::error::AppError;
+use crate::utils::etag::{self, Preconditions, Tagged, TaggedStatus};
+use crate::utils::policy::{self, Permission};
+use crate::utils::unit_of_work;
+use crate::utils::validation::{is_single_line, Rule, Validate, Validator};
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection},
+        Path, State,
+    },
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct TodoPath {
+    id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct TodoTagPath {
+    id: i32,
+    name: String,
+}
+
+#[derive(Deserialize)]
+pub struct TagRequest {
+    name: String,
+}
+
+const NAME_RULES: &[Rule] = &[
+    Rule::Required,
+    Rule::NotBlank,
+    Rule::MaxLength(50),
+    Rule::AllowedCharacters(is_single_line, "printable characters"),
+];
+
+impl Validate for TagRequest {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .field("name", Some(&self.name), NAME_RULES)
+            .finish()
+    }
+}
+
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+) -> Result<(StatusCode, Json<Vec<views::Tag>>), AppError> {
+    policy::authorize(&auth_user, Permission::ReadOwnTodos)?;
+
+    let tags = TagService::new(auth_user.id).list(&state.db_pool).await?;
+
+    Ok((StatusCode::OK, Json(tags.into_iter().map(views::Tag::from).collect())))
+}
+
+pub async fn add_to_todo(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    preconditions: Preconditions,
+    path: Result<Path<TodoPath>, PathRejection>,
+    request: Result<Json<TagRequest>, JsonRejection>,
+) -> Result<Tagged<todos::views::Todo>, AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Path(TodoPath { id }) = path?;
+    let Json(request) = request?;
+    request.validate()?;
+
+    let name = models::normalize_name(&request.name);
+
+    let todo_service = TodoService::new(auth_user.id);
+    let tag_service = TagService::new(auth_user.id);
+    let todo = unit_of_work::run(&state.db_pool, |transaction| {
+        Box::pin(async move {
+            let todo = todo_service.find_for_update(&mut **transaction, id).await?;
+            preconditions.check_match(todo.version)?;
+            tag_service.add_to_todo(&mut **transaction, todo.id, &name).await?;
+            Ok(todo_service.find(&mut **transaction, todo.id).await?)
+        })
+    })
+    .await?;
+
+    Ok(etag::tagged(StatusCode::OK, todo.version, todos::views::Todo::from(todo)))
+}
+
+pub async fn remove_from_todo(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    preconditions: Preconditions,
+    path: Result<Path<TodoTagPath>, PathRejection>,
+) -> Result<TaggedStatus, AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Path(TodoTagPath { id, name }) = path?;
+
+    let name = models::normalize_name(&name);
+
+    let todo_service = TodoService::new(auth_user.id);
+    let tag_service = TagService::new(auth_user.id);
+    let version = unit_of_work::run(&state.db_pool, |transaction| {
+        Box::pin(async move {
+            let todo = todo_service.find_for_update(&mut **transaction, id).await?;
+            preconditions.check_match(todo.version)?;
+            let removed = tag_service
+                .remove_from_todo(&mut **transaction, todo.id, &name)
+                .await?;
+            if removed == 0 {
+                return Err(AppError::NotFound);
+            }
+            Ok(todo_service.version(&mut **transaction, todo.id).await?)
+        })
+    })
+    .await?;
+
+    Ok((StatusCode::NO_CONTENT, etag::header(version)))
+}
@@ -0,0 +1,12 @@
+#[derive(Debug, sqlx::FromRow)]
+pub struct Tag {
+    pub id: i32,
+    pub name: String,
+    #[sqlx(default)]
+    pub usage_count: i64,
+}
+
+// Tags are stored lower-cased so "Urgent" and "urgent" are the same tag.
+pub fn normalize_name(name: &str) -> String {
+    name.trim().to_lowercase()
+}
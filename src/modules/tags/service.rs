@@ -0,0 +1,109 @@
+use std::future::Future;
+
+use sqlx::{Acquire, Postgres};
+
+use crate::modules::todos::service::touch;
+
+use super::models;
+
+/// Manages the tags of a single user and which of their todos carry them.
+///
+/// Like `TodoService`, every method runs on the connection it is given, so
+/// that a handler can lock the todo and tag it in one unit of work.
+pub struct TagService {
+    owner_id: i32,
+}
+impl TagService {
+    pub fn new(owner_id: i32) -> Self {
+        Self { owner_id }
+    }
+
+    pub fn list<'a, 'c, A>(
+        &'a self,
+        db: A,
+    ) -> impl Future<Output = Result<Vec<models::Tag>, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            sqlx::query_as::<_, models::Tag>(
+                "SELECT tags.id, tags.name, COUNT(todos.id) AS usage_count FROM tags \
+                LEFT JOIN (todo_tags JOIN todos ON todos.id = todo_tags.todo_id AND todos.deleted_at IS NULL) \
+                ON todo_tags.tag_id = tags.id \
+                WHERE tags.owner_id = $1 GROUP BY tags.id ORDER BY tags.name;",
+            )
+            .bind(self.owner_id)
+            .fetch_all(&mut *conn)
+            .await
+        }
+    }
+
+    // Creates the tag on first use. Tagging a todo twice is a no-op.
+    pub fn add_to_todo<'a, 'c, A>(
+        &'a self,
+        db: A,
+        todo_id: i32,
+        name: &'a str,
+    ) -> impl Future<Output = Result<(), sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            // A savepoint when `db` is a transaction already.
+            let mut transaction = db.begin().await?;
+            let tag_id = sqlx::query_scalar::<_, i32>(
+                "INSERT INTO tags (owner_id, name) VALUES ($1, $2) \
+                ON CONFLICT (owner_id, name) DO UPDATE SET name = EXCLUDED.name RETURNING id;",
+            )
+            .bind(self.owner_id)
+            .bind(name)
+            .fetch_one(&mut *transaction)
+            .await?;
+            let tagged = sqlx::query(
+                "INSERT INTO todo_tags (todo_id, tag_id) \
+                SELECT id, $1 FROM todos WHERE id = $2 AND owner_id = $3 AND deleted_at IS NULL \
+                ON CONFLICT DO NOTHING;",
+            )
+            .bind(tag_id)
+            .bind(todo_id)
+            .bind(self.owner_id)
+            .execute(&mut *transaction)
+            .await?
+            .rows_affected();
+            if tagged > 0 {
+                touch(&mut transaction, todo_id).await?;
+            }
+            transaction.commit().await
+        }
+    }
+
+    pub fn remove_from_todo<'a, 'c, A>(
+        &'a self,
+        db: A,
+        todo_id: i32,
+        name: &'a str,
+    ) -> impl Future<Output = Result<u64, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut transaction = db.begin().await?;
+            let removed = sqlx::query(
+                "DELETE FROM todo_tags USING tags WHERE todo_tags.tag_id = tags.id \
+                AND todo_tags.todo_id = $1 AND tags.owner_id = $2 AND tags.name = $3;",
+            )
+            .bind(todo_id)
+            .bind(self.owner_id)
+            .bind(name)
+            .execute(&mut *transaction)
+            .await?
+            .rows_affected();
+            if removed > 0 {
+                touch(&mut transaction, todo_id).await?;
+            }
+            transaction.commit().await?;
+            Ok(removed)
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use serde::Serialize;
+
+use super::models;
+
+#[derive(Serialize)]
+pub struct Tag {
+    pub id: i32,
+    pub name: String,
+    pub usage_count: i64,
+}
+
+impl From<models::Tag> for Tag {
+    fn from(tag: models::Tag) -> Self {
+        Tag {
+            id: tag.id,
+            name: tag.name,
+            usage_count: tag.usage_count,
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use std::sync::Arc;
+
+use super::{models, service::TodoItemService, views};
+use crate::configs::state::AppState;
+use crate::modules::todos::service::TodoService;
+use crate::utils::auth::AuthUser;
+use crate::utils::error::AppError;
+use crate::utils::etag::{self, Preconditions, Tagged, TaggedStatus};
+use crate::utils::patch::Patch;
+use crate::utils::policy::{self, Permission};
+use crate::utils::unit_of_work;
+use crate::utils::validation::{is_single_line, Rule, Validate, Validator};
+use crate::views::errors::FieldViolation;
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection},
+        Path, State,
+    },
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct TodoPath {
+    id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct TodoItemPath {
+    id: i32,
+    item_id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct TodoItemRequest {
+    title: String,
+}
+
+#[derive(Deserialize)]
+pub struct TodoItemPatchRequest {
+    #[serde(default)]
+    title: Patch<String>,
+    #[serde(default)]
+    done: Patch<bool>,
+}
+
+impl From<TodoItemPatchRequest> for models::TodoItemChanges {
+    fn from(request: TodoItemPatchRequest) -> Self {
+        models::TodoItemChanges {
+            title: request.title.into_value(),
+            done: request.done.into_value(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ReorderRequest {
+    item_ids: Vec<i32>,
+}
+
+const TITLE_RULES: &[Rule] = &[
+    Rule::Required,
+    Rule::NotBlank,
+    Rule::MaxLength(255),
+    Rule::AllowedCharacters(is_single_line, "printable characters"),
+];
+
+impl Validate for TodoItemRequest {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .field("title", Some(&self.title), TITLE_RULES)
+            .finish()
+    }
+}
+
+impl Validate for TodoItemPatchRequest {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .not_null("title", &self.title)
+            .patch_field("title", &self.title, TITLE_RULES)
+            .not_null("done", &self.done)
+            .finish()
+    }
+}
+
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    path: Result<Path<TodoPath>, PathRejection>,
+) -> Result<(StatusCode, Json<Vec<views::TodoItem>>), AppError> {
+    policy::authorize(&auth_user, Permission::ReadOwnTodos)?;
+    let Path(TodoPath { id }) = path?;
+
+    let todo = TodoService::new(auth_user.id).find(&state.db_pool, id).await?;
+    let items = TodoItemService::new(todo.id).list(&state.db_pool).await?;
+
+    Ok((StatusCode::OK, Json(items.into_iter().map(views::TodoItem::from).collect())))
+}
+
+pub async fn post(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    preconditions: Preconditions,
+    path: Result<Path<TodoPath>, PathRejection>,
+    request: Result<Json<TodoItemRequest>, JsonRejection>,
+) -> Result<Tagged<views::TodoItem>, AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Path(TodoPath { id }) = path?;
+    let Json(request) = request?;
+    request.validate()?;
+
+    let todo_service = TodoService::new(auth_user.id);
+    let (item, version) = unit_of_work::run(&state.db_pool, |transaction| {
+        Box::pin(async move {
+            let todo = todo_service.find_for_update(&mut **transaction, id).await?;
+            preconditions.check_match(todo.version)?;
+            let item = TodoItemService::new(todo.id)
+                .create(&mut **transaction, &request.title)
+                .await?;
+            Ok((item, todo_service.version(&mut **transaction, todo.id).await?))
+        })
+    })
+    .await?;
+
+    Ok(etag::tagged(StatusCode::CREATED, version, views::TodoItem::from(item)))
+}
+
+pub async fn patch(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    preconditions: Preconditions,
+    path: Result<Path<TodoItemPath>, PathRejection>,
+    request: Result<Json<TodoItemPatchRequest>, JsonRejection>,
+) -> Result<Tagged<views::TodoItem>, AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Path(TodoItemPath { id, item_id }) = path?;
+    let Json(request) = request?;
+    request.validate()?;
+
+    let todo_service = TodoService::new(auth_user.id);
+    let (item, version) = unit_of_work::run(&state.db_pool, |transaction| {
+        Box::pin(async move {
+            let todo = todo_service.find_for_update(&mut **transaction, id).await?;
+            preconditions.check_match(todo.version)?;
+            let changes = request.into();
+            let item = TodoItemService::new(todo.id)
+                .patch(&mut **transaction, item_id, &changes)
+                .await?;
+            Ok((item, todo_service.version(&mut **transaction, todo.id).await?))
+        })
+    })
+    .await?;
+
+    Ok(etag::tagged(StatusCode::OK, version, views::TodoItem::from(item)))
+}
+
+pub async fn delete(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    preconditions: Preconditions,
+    path: Result<Path<TodoItemPath>, PathRejection>,
+) -> Result<TaggedStatus, AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Path(TodoItemPath { id, item_id }) = path?;
+
+    let todo_service = TodoService::new(auth_user.id);
+    let version = unit_of_work::run(&state.db_pool, |transaction| {
+        Box::pin(async move {
+            let todo = todo_service.find_for_update(&mut **transaction, id).await?;
+            preconditions.check_match(todo.version)?;
+            let deleted = TodoItemService::new(todo.id)
+                .delete(&mut **transaction, item_id)
+                .await?;
+            if deleted == 0 {
+                return Err(AppError::NotFound);
+            }
+            Ok(todo_service.version(&mut **transaction, todo.id).await?)
+        })
+    })
+    .await?;
+
+    Ok((StatusCode::NO_CONTENT, etag::header(version)))
+}
+
+pub async fn reorder(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    preconditions: Preconditions,
+    path: Result<Path<TodoPath>, PathRejection>,
+    request: Result<Json<ReorderRequest>, JsonRejection>,
+) -> Result<Tagged<Vec<views::TodoItem>>, AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Path(TodoPath { id }) = path?;
+    let Json(request) = request?;
+
+    let todo_service = TodoService::new(auth_user.id);
+    let (items, version) = unit_of_work::run(&state.db_pool, |transaction| {
+        Box::pin(async move {
+            let todo = todo_service.find_for_update(&mut **transaction, id).await?;
+            preconditions.check_match(todo.version)?;
+            let todo_item_service = TodoItemService::new(todo.id);
+            let mut existing_ids: Vec<i32> = todo_item_service
+                .list(&mut **transaction)
+                .await?
+                .iter()
+                .map(|item| item.id)
+                .collect();
+            let mut requested_ids = request.item_ids.clone();
+            existing_ids.sort();
+            requested_ids.sort();
+            if existing_ids != requested_ids {
+                return Err(AppError::Validation(vec![FieldViolation {
+                    field: "item_ids".to_string(),
+                    reason: "must list every item of the todo exactly once".to_string(),
+                }]));
+            }
+            let items = todo_item_service
+                .reorder(&mut **transaction, &request.item_ids)
+                .await?;
+            Ok((items, todo_service.version(&mut **transaction, todo.id).await?))
+        })
+    })
+    .await?;
+
+    let items = items.into_iter().map(views::TodoItem::from).collect();
+    Ok(etag::tagged(StatusCode::OK, version, items))
+}
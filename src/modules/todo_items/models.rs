@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct TodoItem {
+    pub id: i32,
+    pub title: String,
+    pub done: bool,
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+pub struct TodoItemChanges {
+    pub title: Option<String>,
+    pub done: Option<bool>,
+}
+
+impl TodoItemChanges {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.done.is_none()
+    }
+}
@@ -0,0 +1,191 @@
+use std::future::Future;
+
+use sqlx::{Acquire, Connection, Postgres, QueryBuilder};
+
+use crate::modules::todos::service::touch;
+
+use super::models;
+
+/// Reads and writes the checklist items of a single todo. Callers lock the
+/// todo with `TodoService::find_for_update` in the same unit of work first,
+/// which also checks that it belongs to the user. Every change bumps the
+/// version of the todo, whose progress it affects.
+pub struct TodoItemService {
+    todo_id: i32,
+}
+impl TodoItemService {
+    pub fn new(todo_id: i32) -> Self {
+        Self { todo_id }
+    }
+
+    pub fn list<'a, 'c, A>(
+        &'a self,
+        db: A,
+    ) -> impl Future<Output = Result<Vec<models::TodoItem>, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            sqlx::query_as::<_, models::TodoItem>(
+                "SELECT * FROM todo_items WHERE todo_id = $1 ORDER BY position;",
+            )
+            .bind(self.todo_id)
+            .fetch_all(&mut *conn)
+            .await
+        }
+    }
+
+    pub fn find<'a, 'c, A>(
+        &'a self,
+        db: A,
+        id: i32,
+    ) -> impl Future<Output = Result<models::TodoItem, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            sqlx::query_as::<_, models::TodoItem>(
+                "SELECT * FROM todo_items WHERE id = $1 AND todo_id = $2;",
+            )
+            .bind(id)
+            .bind(self.todo_id)
+            .fetch_one(&mut *conn)
+            .await
+        }
+    }
+
+    // New items go to the end of the checklist.
+    pub fn create<'a, 'c, A>(
+        &'a self,
+        db: A,
+        title: &'a str,
+    ) -> impl Future<Output = Result<models::TodoItem, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            // A savepoint when `db` is a transaction already.
+            let mut transaction = db.begin().await?;
+            let item = sqlx::query_as::<_, models::TodoItem>(
+                "INSERT INTO todo_items (todo_id, title, position) \
+                SELECT $1, $2, COALESCE(MAX(position) + 1, 0) FROM todo_items WHERE todo_id = $1 \
+                RETURNING *;",
+            )
+            .bind(self.todo_id)
+            .bind(title)
+            .fetch_one(&mut *transaction)
+            .await?;
+            touch(&mut transaction, self.todo_id).await?;
+            transaction.commit().await?;
+            Ok(item)
+        }
+    }
+
+    pub fn patch<'a, 'c, A>(
+        &'a self,
+        db: A,
+        id: i32,
+        changes: &'a models::TodoItemChanges,
+    ) -> impl Future<Output = Result<models::TodoItem, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            if changes.is_empty() {
+                return self.find(&mut *conn, id).await;
+            }
+
+            let mut query =
+                QueryBuilder::<Postgres>::new("UPDATE todo_items SET updated_at = now()");
+            if let Some(title) = &changes.title {
+                query.push(", title = ").push_bind(title);
+            }
+            if let Some(done) = changes.done {
+                query.push(", done = ").push_bind(done);
+            }
+            query
+                .push(" WHERE id = ")
+                .push_bind(id)
+                .push(" AND todo_id = ")
+                .push_bind(self.todo_id)
+                .push(" RETURNING *;");
+
+            let mut transaction = Connection::begin(&mut *conn).await?;
+            let item = query
+                .build_query_as::<models::TodoItem>()
+                .fetch_one(&mut *transaction)
+                .await?;
+            touch(&mut transaction, self.todo_id).await?;
+            transaction.commit().await?;
+            Ok(item)
+        }
+    }
+
+    // Closes the gap left by the deleted item so positions stay contiguous.
+    pub fn delete<'a, 'c, A>(
+        &'a self,
+        db: A,
+        id: i32,
+    ) -> impl Future<Output = Result<u64, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut transaction = db.begin().await?;
+            let position = sqlx::query_scalar::<_, i32>(
+                "DELETE FROM todo_items WHERE id = $1 AND todo_id = $2 RETURNING position;",
+            )
+            .bind(id)
+            .bind(self.todo_id)
+            .fetch_optional(&mut *transaction)
+            .await?;
+            let Some(position) = position else {
+                return Ok(0);
+            };
+            sqlx::query(
+                "UPDATE todo_items SET position = position - 1 WHERE todo_id = $1 AND position > $2;",
+            )
+            .bind(self.todo_id)
+            .bind(position)
+            .execute(&mut *transaction)
+            .await?;
+            touch(&mut transaction, self.todo_id).await?;
+            transaction.commit().await?;
+            Ok(1)
+        }
+    }
+
+    // `ids` must list every item of the todo exactly once, in the new order.
+    pub fn reorder<'a, 'c, A>(
+        &'a self,
+        db: A,
+        ids: &'a [i32],
+    ) -> impl Future<Output = Result<Vec<models::TodoItem>, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut transaction = db.begin().await?;
+            let moved = sqlx::query(
+                "UPDATE todo_items SET position = ordered.position - 1, updated_at = now() \
+                FROM unnest($1::integer[]) WITH ORDINALITY AS ordered (id, position) \
+                WHERE todo_items.id = ordered.id AND todo_items.todo_id = $2 \
+                AND todo_items.position <> ordered.position - 1;",
+            )
+            .bind(ids)
+            .bind(self.todo_id)
+            .execute(&mut *transaction)
+            .await?
+            .rows_affected();
+            if moved > 0 {
+                touch(&mut transaction, self.todo_id).await?;
+            }
+            let items = self.list(&mut *transaction).await?;
+            transaction.commit().await?;
+            Ok(items)
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::models;
+
+#[derive(Serialize)]
+pub struct TodoItem {
+    pub id: i32,
+    pub title: String,
+    pub done: bool,
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<models::TodoItem> for TodoItem {
+    fn from(item: models::TodoItem) -> Self {
+        TodoItem {
+            id: item.id,
+            title: item.title,
+            done: item.done,
+            position: item.position,
+            created_at: item.created_at,
+            updated_at: item.updated_at,
+        }
+    }
+}
@@ -1,180 +1,780 @@
-use std::cmp;
 use std::sync::Arc;
 
-use super::{service::TodoService, views};
+use super::{models, service::TodoService, views};
 use crate::configs::state::AppState;
-use crate::utils::error::build_response_from_path_rejection;
-use crate::constants::error_response::{
-        GENERIC_INTERNAL_SERVER_ERROR_RESPONSE, GENERIC_NOT_FOUND_ERROR_RESPONSE,
-    };
-use axum::body::Body;
-use axum::extract::Query;
+use crate::modules::lists::service::ListService;
+use crate::modules::tags::models::normalize_name;
+use crate::utils::auth::AuthUser;
+use crate::utils::error::AppError;
+use crate::utils::cursor::Cursor;
+use crate::utils::etag::{self, Preconditions, Tagged};
+use crate::utils::patch::Patch;
+use crate::utils::policy::{self, Permission};
+use crate::utils::query::Query;
+use crate::utils::unit_of_work;
+use crate::views::errors::{BadRequestErrorMessage, FieldViolation};
+use crate::views::pagination::{CursorPagination, Page, Pagination};
+use crate::utils::validation::{is_multi_line, is_single_line, Rule, Validate, Validator};
+use chrono::{DateTime, Utc};
 use axum::{
-    extract::{rejection::PathRejection, Path, State},
+    extract::{
+        rejection::{JsonRejection, PathRejection},
+        Path, State,
+    },
     http::StatusCode,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::Deserialize;
+use sqlx::{PgConnection, Pool, Postgres};
+
+#[derive(Deserialize)]
+pub struct TodoPath {
+    id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct ListPath {
+    id: i32,
+}
 
 #[derive(Deserialize)]
 pub struct TodoRequest {
     title: String,
     content: String,
+    #[serde(default)]
+    completed: bool,
+    due_at: Option<DateTime<Utc>>,
+    list_id: Option<i32>,
+}
+
+impl From<TodoRequest> for models::TodoFields {
+    fn from(request: TodoRequest) -> Self {
+        models::TodoFields {
+            title: request.title,
+            content: request.content,
+            completed: request.completed,
+            due_at: request.due_at,
+            list_id: request.list_id,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct TodoPatchRequest {
+    #[serde(default)]
+    title: Patch<String>,
+    #[serde(default)]
+    content: Patch<String>,
+    #[serde(default)]
+    completed: Patch<bool>,
+    #[serde(default)]
+    due_at: Patch<DateTime<Utc>>,
+    #[serde(default)]
+    list_id: Patch<i32>,
+}
+
+impl From<TodoPatchRequest> for models::TodoChanges {
+    fn from(request: TodoPatchRequest) -> Self {
+        models::TodoChanges {
+            title: request.title.into_value(),
+            content: request.content.into_value(),
+            completed: request.completed.into_value(),
+            due_at: request.due_at.into_option(),
+            list_id: request.list_id.into_option(),
+        }
+    }
+}
+
+const TITLE_RULES: &[Rule] = &[
+    Rule::Required,
+    Rule::NotBlank,
+    Rule::MaxLength(255),
+    Rule::AllowedCharacters(is_single_line, "printable characters"),
+];
+
+const CONTENT_RULES: &[Rule] = &[
+    Rule::Required,
+    Rule::MaxLength(10_000),
+    Rule::AllowedCharacters(is_multi_line, "printable characters, tabs and line breaks"),
+];
+
+impl Validate for TodoRequest {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .field("title", Some(&self.title), TITLE_RULES)
+            .field("content", Some(&self.content), CONTENT_RULES)
+            .finish()
+    }
+}
+
+impl Validate for TodoPatchRequest {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .not_null("title", &self.title)
+            .patch_field("title", &self.title, TITLE_RULES)
+            .not_null("content", &self.content)
+            .patch_field("content", &self.content, CONTENT_RULES)
+            .not_null("completed", &self.completed)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BulkOperationRequest {
+    Create(TodoRequest),
+    Update {
+        id: i32,
+        #[serde(flatten)]
+        changes: TodoPatchRequest,
+    },
+    Delete {
+        id: i32,
+    },
+}
+
+#[derive(Deserialize)]
+pub struct BulkRequest {
+    operations: Vec<BulkOperationRequest>,
 }
 
 #[derive(Deserialize)]
-pub struct Pagination {
+pub struct BulkQuery {
+    partial: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct MoveRequest {
+    before: Option<i32>,
+    after: Option<i32>,
+}
+
+impl MoveRequest {
+    fn target(&self) -> Result<(i32, models::Placement), AppError> {
+        match (self.before, self.after) {
+            (Some(before), None) => Ok((before, models::Placement::Before)),
+            (None, Some(after)) => Ok((after, models::Placement::After)),
+            _ => Err(AppError::Validation(vec![FieldViolation {
+                field: "before".to_string(),
+                reason: "exactly one of before and after is required".to_string(),
+            }])),
+        }
+    }
+}
+
+#[derive(Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PaginationMode {
+    Offset,
+    Cursor,
+}
+
+#[derive(Deserialize)]
+pub struct ListQuery {
     limit: Option<i64>,
     offset: Option<i64>,
+    pagination: Option<PaginationMode>,
+    cursor: Option<String>,
+    include_total: Option<bool>,
+    q: Option<String>,
+    completed: Option<bool>,
+    due_before: Option<DateTime<Utc>>,
+    due_after: Option<DateTime<Utc>>,
+    list_id: Option<i32>,
+    #[serde(default)]
+    tag: Vec<String>,
+    tag_match: Option<models::TagMatch>,
+    sort: Option<String>,
+}
+
+impl TryFrom<ListQuery> for models::TodoFilter {
+    type Error = AppError;
+
+    fn try_from(query: ListQuery) -> Result<Self, Self::Error> {
+        let sort = match query.sort.as_deref() {
+            None => models::TodoSort::default(),
+            Some(sort) => models::TodoSort::parse(sort).ok_or_else(|| {
+                AppError::BadRequest(BadRequestErrorMessage::invalid_query(
+                    "sort".to_string(),
+                    format!(
+                        "expected: <field>:<asc|desc> with field one of {}",
+                        models::SortField::NAMES.join(", ")
+                    ),
+                ))
+            })?,
+        };
+        let mut tags: Vec<String> = query
+            .tag
+            .iter()
+            .map(|tag| normalize_name(tag))
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        tags.sort();
+        tags.dedup();
+        Ok(models::TodoFilter {
+            search: query.q.filter(|q| !q.trim().is_empty()),
+            completed: query.completed,
+            due_before: query.due_before,
+            due_after: query.due_after,
+            list_id: query.list_id,
+            tags,
+            tag_match: query.tag_match.unwrap_or_default(),
+            sort,
+        })
+    }
 }
 
 pub async fn get(
     State(state): State<Arc<AppState>>,
-    id: Result<Path<i32>, PathRejection>,
-) -> (StatusCode, impl IntoResponse) {
-    let id = match id {
-        Err(path_rejection_error) => {
-            return build_response_from_path_rejection("id", path_rejection_error)
+    auth_user: AuthUser,
+    preconditions: Preconditions,
+    path: Result<Path<TodoPath>, PathRejection>,
+) -> Result<Response, AppError> {
+    policy::authorize(&auth_user, Permission::ReadOwnTodos)?;
+    let Path(TodoPath { id }) = path?;
+
+    let todo = TodoService::new(auth_user.id).find(&state.db_pool, id).await?;
+    if preconditions.is_not_modified(todo.version) {
+        return Ok((StatusCode::NOT_MODIFIED, etag::header(todo.version)).into_response());
+    }
+
+    Ok(etag::tagged(StatusCode::OK, todo.version, views::Todo::from(todo)).into_response())
+}
+
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Query(query): Query<ListQuery>,
+) -> Result<(StatusCode, Json<Page<views::Todo>>), AppError> {
+    policy::authorize(&auth_user, Permission::ReadOwnTodos)?;
+    let (limit, requested_limit) = state
+        .settings
+        .pagination
+        .for_module("todos")
+        .resolve_query(query.limit, query.offset)?;
+    let include_total = query.include_total.unwrap_or(true);
+    let cursor = match (&query.pagination, query.cursor.clone()) {
+        (Some(PaginationMode::Cursor), None) => Some(String::new()),
+        (_, cursor) => cursor,
+    };
+    let offset = query.offset;
+    let filter = models::TodoFilter::try_from(query)?;
+
+    let todo_service = TodoService::new(auth_user.id);
+    let db_pool = &state.db_pool;
+    let page = match (cursor, offset) {
+        (Some(_), Some(_)) => {
+            return Err(AppError::BadRequest(BadRequestErrorMessage::invalid_query(
+                "offset".to_string(),
+                "offset cannot be combined with cursor".to_string(),
+            )))
         }
-        Ok(value) => value.0,
+        (Some(cursor), None) => Page::Cursor(CursorPagination {
+            requested_limit,
+            ..list_by_cursor(db_pool, &todo_service, &filter, limit, &cursor, include_total).await?
+        }),
+        (None, offset) => Page::Offset(Pagination {
+            requested_limit,
+            ..list_by_offset(
+                db_pool,
+                &todo_service,
+                &filter,
+                limit,
+                offset.unwrap_or(0),
+                include_total,
+            )
+            .await?
+        }),
     };
 
-    let todo_service = TodoService::new(state);
+    Ok((StatusCode::OK, Json(page)))
+}
 
-    match todo_service.find(id).await {
-        Err(_) => GENERIC_NOT_FOUND_ERROR_RESPONSE.into_response(),
-        Ok(todo) => {
-            let view = views::Todo {
-                id: todo.id,
-                title: todo.title,
-                content: todo.content,
-            };
-            (StatusCode::OK, Json(view).into_response())
+async fn list_by_offset(
+    db_pool: &Pool<Postgres>,
+    todo_service: &TodoService,
+    filter: &models::TodoFilter,
+    limit: i64,
+    offset: i64,
+    include_total: bool,
+) -> Result<Pagination<views::Todo>, AppError> {
+    let (list, total) = match include_total {
+        true => {
+            let list = todo_service.list(db_pool, filter, limit, offset).await?;
+            let total = list.first().map_or(0, |todo| todo.total);
+            (list, Some(total))
         }
+        false => (todo_service.list_page(db_pool, filter, limit, offset, None).await?, None),
+    };
+
+    Ok(Pagination {
+        limit,
+        requested_limit: None,
+        offset,
+        total,
+        items: list.into_iter().map(views::Todo::from).collect(),
+    })
+}
+
+async fn list_by_cursor(
+    db_pool: &Pool<Postgres>,
+    todo_service: &TodoService,
+    filter: &models::TodoFilter,
+    limit: i64,
+    cursor: &str,
+    include_total: bool,
+) -> Result<CursorPagination<views::Todo>, AppError> {
+    let after = match cursor {
+        "" => None,
+        cursor => Some(decode_cursor(cursor, &filter.sort)?),
+    };
+
+    let mut list = todo_service
+        .list_page(db_pool, filter, limit + 1, 0, after.as_ref())
+        .await?;
+    let next_cursor = match list.len() as i64 > limit {
+        false => None,
+        true => {
+            list.truncate(limit as usize);
+            list.last().map(|todo| {
+                let position = filter.sort.position_of(todo);
+                Cursor {
+                    sort: filter.sort.to_string(),
+                    value: position.value,
+                    id: position.id,
+                }
+                .encode()
+            })
+        }
+    };
+    let total = match include_total {
+        true => Some(todo_service.count(db_pool, filter).await?),
+        false => None,
+    };
+
+    Ok(CursorPagination {
+        limit,
+        requested_limit: None,
+        next_cursor,
+        total,
+        items: list.into_iter().map(views::Todo::from).collect(),
+    })
+}
+
+fn decode_cursor(cursor: &str, sort: &models::TodoSort) -> Result<models::KeysetPosition, AppError> {
+    match Cursor::decode(cursor) {
+        Some(cursor) if cursor.sort == sort.to_string() => Ok(models::KeysetPosition {
+            value: cursor.value,
+            id: cursor.id,
+        }),
+        _ => Err(AppError::BadRequest(BadRequestErrorMessage::invalid_query(
+            "cursor".to_string(),
+            "cursor is malformed or was issued for a different sort".to_string(),
+        ))),
     }
 }
 
-pub async fn list(
+pub async fn post(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    request: Result<Json<TodoRequest>, JsonRejection>,
+) -> Result<Tagged<views::Todo>, AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Json(request) = request?;
+
+    create(state, auth_user, request).await
+}
+
+async fn create(
+    state: Arc<AppState>,
+    auth_user: AuthUser,
+    request: TodoRequest,
+) -> Result<Tagged<views::Todo>, AppError> {
+    request.validate()?;
+
+    let todo_service = TodoService::new(auth_user.id);
+    let todo = unit_of_work::run(&state.db_pool, |transaction| {
+        Box::pin(async move {
+            ensure_own_list(transaction, auth_user.id, request.list_id).await?;
+            let fields = request.into();
+            Ok(todo_service.create(&mut **transaction, &fields).await?)
+        })
+    })
+    .await?;
+
+    Ok(etag::tagged(StatusCode::CREATED, todo.version, views::Todo::from(todo)))
+}
+
+pub async fn put(
     State(state): State<Arc<AppState>>,
-    Query(pagination): Query<Pagination>,
-) -> (StatusCode, impl IntoResponse) {
-    let limit = cmp::min(pagination.limit.unwrap_or(10), 10);
-    let offset = pagination.offset.unwrap_or(0);
-
-    let todo_service = TodoService::new(state);
-    let mut todos: Vec<views::Todo> = vec![];
-    let total: i64;
-
-    match todo_service.list(limit, offset).await {
-        Err(_) => return GENERIC_INTERNAL_SERVER_ERROR_RESPONSE.into_response(),
-        Ok(list) => {
-            total = match list.is_empty() {
-                true => 0,
-                false => list[0].total
+    auth_user: AuthUser,
+    preconditions: Preconditions,
+    path: Result<Path<TodoPath>, PathRejection>,
+    request: Result<Json<TodoRequest>, JsonRejection>,
+) -> Result<Tagged<views::Todo>, AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Path(TodoPath { id }) = path?;
+    let Json(request) = request?;
+    request.validate()?;
+
+    let todo_service = TodoService::new(auth_user.id);
+    let updated_todo = unit_of_work::run(&state.db_pool, |transaction| {
+        Box::pin(async move {
+            let todo = todo_service.find_for_update(&mut **transaction, id).await?;
+            preconditions.check_match(todo.version)?;
+            ensure_own_list(transaction, auth_user.id, request.list_id).await?;
+            let fields = request.into();
+            Ok(todo_service.update(&mut **transaction, todo.id, &fields).await?)
+        })
+    })
+    .await?;
+
+    Ok(etag::tagged(StatusCode::OK, updated_todo.version, views::Todo::from(updated_todo)))
+}
+
+pub async fn patch(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    preconditions: Preconditions,
+    path: Result<Path<TodoPath>, PathRejection>,
+    request: Result<Json<TodoPatchRequest>, JsonRejection>,
+) -> Result<Tagged<views::Todo>, AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Path(TodoPath { id }) = path?;
+    let Json(request) = request?;
+    request.validate()?;
+
+    let todo_service = TodoService::new(auth_user.id);
+    let patched_todo = unit_of_work::run(&state.db_pool, |transaction| {
+        Box::pin(async move {
+            let todo = todo_service.find_for_update(&mut **transaction, id).await?;
+            preconditions.check_match(todo.version)?;
+            ensure_own_list(transaction, auth_user.id, request.list_id.as_option()).await?;
+            let changes = request.into();
+            Ok(todo_service.patch(&mut **transaction, todo.id, &changes).await?)
+        })
+    })
+    .await?;
+
+    Ok(etag::tagged(StatusCode::OK, patched_todo.version, views::Todo::from(patched_todo)))
+}
+
+pub async fn move_to(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    path: Result<Path<TodoPath>, PathRejection>,
+    request: Result<Json<MoveRequest>, JsonRejection>,
+) -> Result<Tagged<views::Todo>, AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Path(TodoPath { id }) = path?;
+    let Json(request) = request?;
+    let (target_id, placement) = request.target()?;
+
+    let todo_service = TodoService::new(auth_user.id);
+    let moved_todo = unit_of_work::run(&state.db_pool, |transaction| {
+        Box::pin(async move {
+            let todo = todo_service.find_for_update(&mut **transaction, id).await?;
+            let field = match placement {
+                models::Placement::Before => "before",
+                models::Placement::After => "after",
             };
-            for todo in list.iter() {
-                todos.push(views::Todo {
-                    id: todo.id,
-                    title: todo.title.to_string(),
-                    content: todo.content.to_string(),
-                });
+            let is_other_todo = target_id != todo.id
+                && match todo_service.find(&mut **transaction, target_id).await {
+                    Err(sqlx::Error::RowNotFound) => false,
+                    result => result.map(|_| true)?,
+                };
+            if !is_other_todo {
+                return Err(AppError::Validation(vec![FieldViolation {
+                    field: field.to_string(),
+                    reason: "must reference another one of your todos".to_string(),
+                }]));
             }
-        }
-    }
+            Ok(todo_service
+                .move_to(&mut **transaction, todo.id, target_id, placement)
+                .await?)
+        })
+    })
+    .await?;
 
-    (
-        StatusCode::OK,
-        Json(crate::views::pagination::Pagination {
-            limit,
-            offset,
-            total,
-            items: todos,
+    Ok(etag::tagged(StatusCode::OK, moved_todo.version, views::Todo::from(moved_todo)))
+}
+
+pub async fn delete(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    preconditions: Preconditions,
+    path: Result<Path<TodoPath>, PathRejection>,
+) -> Result<StatusCode, AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Path(TodoPath { id }) = path?;
+
+    let todo_service = TodoService::new(auth_user.id);
+    unit_of_work::run(&state.db_pool, |transaction| {
+        Box::pin(async move {
+            let todo = todo_service.find_for_update(&mut **transaction, id).await?;
+            preconditions.check_match(todo.version)?;
+
+            let pg_query_result = todo_service.delete(&mut **transaction, todo.id).await?;
+            if pg_query_result.rows_affected() != 1_u64 {
+                return Err(AppError::Internal(format!(
+                    "expected to delete 1 todo, deleted {}",
+                    pg_query_result.rows_affected()
+                )));
+            }
+            Ok(())
         })
-        .into_response(),
-    )
+    })
+    .await?;
+    Ok(StatusCode::NO_CONTENT)
 }
 
-pub async fn post(
+// Runs every operation in one transaction, all or nothing unless
+// `?partial=true` is given. Operations that fail validation are reported
+// without reaching the database.
+pub async fn bulk(
     State(state): State<Arc<AppState>>,
-    Json(request): Json<TodoRequest>,
-) -> (StatusCode, impl IntoResponse) {
-    let todo_service = TodoService::new(state);
-
-    match todo_service.create(&request.title, &request.content).await {
-        Err(_) => GENERIC_INTERNAL_SERVER_ERROR_RESPONSE.into_response(),
-        Ok(todo) => {
-            let view = views::Todo {
-                id: todo.id,
-                title: todo.title,
-                content: todo.content,
+    auth_user: AuthUser,
+    Query(query): Query<BulkQuery>,
+    request: Result<Json<BulkRequest>, JsonRejection>,
+) -> Result<(StatusCode, Json<views::BulkResponse>), AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Json(request) = request?;
+    let max_operations = state.settings.bulk.max_operations;
+    if request.operations.is_empty() || request.operations.len() > max_operations {
+        return Err(AppError::Validation(vec![FieldViolation {
+            field: "operations".to_string(),
+            reason: format!("must contain between 1 and {} operations", max_operations),
+        }]));
+    }
+    let partial = query.partial.unwrap_or(false);
+
+    let todo_service = TodoService::new(auth_user.id);
+    let (rejected, outcomes, applicable) = unit_of_work::run(&state.db_pool, |transaction| {
+        Box::pin(async move {
+            // `None` marks the operations that are passed on to the service.
+            let mut rejected = Vec::with_capacity(request.operations.len());
+            let mut operations = Vec::new();
+            for operation in request.operations {
+                match prepare(transaction, auth_user.id, operation).await {
+                    Ok(operation) => {
+                        rejected.push(None);
+                        operations.push(operation);
+                    }
+                    Err(error) => rejected.push(Some(failed(error)?)),
+                }
+            }
+            let outcomes = match !partial && rejected.iter().any(Option::is_some) {
+                true => Vec::new(),
+                false => {
+                    todo_service
+                        .bulk(&mut **transaction, &operations, partial)
+                        .await?
+                }
             };
-            (StatusCode::CREATED, Json(view).into_response())
+            Ok((rejected, outcomes, operations.len()))
+        })
+    })
+    .await?;
+    let committed =
+        partial || (outcomes.len() == applicable && outcomes.iter().all(Result::is_ok));
+
+    let mut outcomes = outcomes.into_iter();
+    let results = rejected
+        .into_iter()
+        .map(|rejected| match rejected {
+            Some(result) => Ok(result),
+            None => match outcomes.next() {
+                Some(Ok(outcome)) if committed => Ok(views::BulkResult::from(outcome)),
+                Some(Err(error)) if committed => Ok(failed_after_commit(error)),
+                Some(Err(error)) => failed(AppError::from(error)),
+                _ => Ok(not_applied()),
+            },
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    let status = match committed {
+        true => StatusCode::OK,
+        false => StatusCode::UNPROCESSABLE_ENTITY,
+    };
+    Ok((status, Json(views::BulkResponse { committed, results })))
+}
+
+async fn prepare(
+    conn: &mut PgConnection,
+    owner_id: i32,
+    operation: BulkOperationRequest,
+) -> Result<models::BulkOperation, AppError> {
+    match operation {
+        BulkOperationRequest::Create(request) => {
+            request.validate()?;
+            ensure_own_list(conn, owner_id, request.list_id).await?;
+            Ok(models::BulkOperation::Create(request.into()))
         }
+        BulkOperationRequest::Update { id, changes } => {
+            changes.validate()?;
+            ensure_own_list(conn, owner_id, changes.list_id.as_option()).await?;
+            Ok(models::BulkOperation::Update {
+                id,
+                changes: changes.into(),
+            })
+        }
+        BulkOperationRequest::Delete { id } => Ok(models::BulkOperation::Delete { id }),
     }
 }
 
-pub async fn put(
-    State(state): State<Arc<AppState>>,
-    id: Result<Path<i32>, PathRejection>,
-    Json(request): Json<TodoRequest>,
-) -> (StatusCode, impl IntoResponse) {
-    let id = match id {
-        Err(path_rejection_error) => {
-            return build_response_from_path_rejection("id", path_rejection_error)
-        }
-        Ok(value) => value.0,
+// Reports a failed operation in its result; errors that are not about the
+// operation itself fail the whole request instead.
+fn failed(error: AppError) -> Result<views::BulkResult, AppError> {
+    let (status, message, errors) = match error {
+        AppError::NotFound => (StatusCode::NOT_FOUND, "not found", Vec::new()),
+        AppError::Conflict => (StatusCode::CONFLICT, "conflict", Vec::new()),
+        AppError::Validation(violations) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "the following fields are invalid",
+            violations,
+        ),
+        error => return Err(error),
     };
+    Ok(views::BulkResult {
+        status: status.as_u16(),
+        todo: None,
+        message: Some(message.to_string()),
+        errors,
+    })
+}
 
-    let todo_service = TodoService::new(state);
-    let result = todo_service.find(id).await;
-
-    match result {
-        Err(sqlx::Error::RowNotFound) => GENERIC_NOT_FOUND_ERROR_RESPONSE.into_response(),
-        Err(_) => GENERIC_INTERNAL_SERVER_ERROR_RESPONSE.into_response(),
-        Ok(todo) => {
-            let updated_todo = match todo_service
-                .update(todo.id, &request.title, &request.content)
-                .await
-            {
-                Err(_) => return GENERIC_INTERNAL_SERVER_ERROR_RESPONSE.into_response(),
-                Ok(updated_todo) => updated_todo,
-            };
-
-            let view = views::Todo {
-                id: updated_todo.id,
-                title: updated_todo.title,
-                content: updated_todo.content,
-            };
-            (StatusCode::OK, Json(view).into_response())
+// Once other operations are committed, failing the whole request would make
+// the client retry and apply them twice, so every error stays in its result.
+fn failed_after_commit(error: sqlx::Error) -> views::BulkResult {
+    failed(AppError::from(error)).unwrap_or_else(|error| {
+        eprintln!("Bulk operation failed {:?}", error);
+        views::BulkResult {
+            status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            todo: None,
+            message: Some("internal server error".to_string()),
+            errors: Vec::new(),
         }
+    })
+}
+
+fn not_applied() -> views::BulkResult {
+    views::BulkResult {
+        status: StatusCode::FAILED_DEPENDENCY.as_u16(),
+        todo: None,
+        message: Some("not applied because another operation failed".to_string()),
+        errors: Vec::new(),
     }
 }
-pub async fn delete(
+
+#[derive(Deserialize)]
+pub struct TrashQuery {
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+pub async fn trash(
     State(state): State<Arc<AppState>>,
-    id: Result<Path<i32>, PathRejection>,
-) -> (StatusCode, impl IntoResponse) {
-    let id = match id {
-        Err(path_rejection_error) => {
-            return build_response_from_path_rejection("id", path_rejection_error)
-        }
-        Ok(value) => value.0,
-    };
+    auth_user: AuthUser,
+    Query(query): Query<TrashQuery>,
+) -> Result<(StatusCode, Json<Pagination<views::TrashedTodo>>), AppError> {
+    policy::authorize(&auth_user, Permission::ReadOwnTodos)?;
+    let (limit, requested_limit) = state
+        .settings
+        .pagination
+        .for_module("todos")
+        .resolve_query(query.limit, query.offset)?;
+    let offset = query.offset.unwrap_or(0);
 
-    let todo_service = TodoService::new(state);
-    let find_result = match todo_service.find(id).await {
-        Err(sqlx::Error::RowNotFound) => return GENERIC_NOT_FOUND_ERROR_RESPONSE.into_response(),
-        Err(_) => return GENERIC_INTERNAL_SERVER_ERROR_RESPONSE.into_response(),
-        Ok(result) => result,
-    };
+    let list = TodoService::new(auth_user.id)
+        .list_trash(&state.db_pool, limit, offset)
+        .await?;
+    let total = list.first().map_or(0, |todo| todo.total);
 
-    match todo_service.delete(find_result.id).await {
-        Err(_) => GENERIC_INTERNAL_SERVER_ERROR_RESPONSE.into_response(),
-        Ok(pg_query_result) => {
-            if pg_query_result.rows_affected() != 1_u64 {
-                return GENERIC_INTERNAL_SERVER_ERROR_RESPONSE.into_response();
-            }
-            (StatusCode::NO_CONTENT, Body::empty().into_response())
-        }
+    Ok((
+        StatusCode::OK,
+        Json(Pagination {
+            limit,
+            requested_limit,
+            offset,
+            total: Some(total),
+            items: list.into_iter().map(views::TrashedTodo::from).collect(),
+        }),
+    ))
+}
+
+pub async fn restore(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    path: Result<Path<TodoPath>, PathRejection>,
+) -> Result<Tagged<views::Todo>, AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Path(TodoPath { id }) = path?;
+
+    let todo = TodoService::new(auth_user.id).restore(&state.db_pool, id).await?;
+    Ok(etag::tagged(StatusCode::OK, todo.version, views::Todo::from(todo)))
+}
+
+pub async fn list_in_list(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    path: Result<Path<ListPath>, PathRejection>,
+    Query(query): Query<ListQuery>,
+) -> Result<(StatusCode, Json<Page<views::Todo>>), AppError> {
+    policy::authorize(&auth_user, Permission::ReadOwnTodos)?;
+    let Path(ListPath { id }) = path?;
+    ListService::new(auth_user.id).find(&state.db_pool, id).await?;
+
+    list(
+        State(state),
+        auth_user,
+        Query(ListQuery {
+            list_id: Some(id),
+            ..query
+        }),
+    )
+    .await
+}
+
+pub async fn post_in_list(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    path: Result<Path<ListPath>, PathRejection>,
+    request: Result<Json<TodoRequest>, JsonRejection>,
+) -> Result<Tagged<views::Todo>, AppError> {
+    policy::authorize(&auth_user, Permission::WriteOwnTodos)?;
+    let Path(ListPath { id }) = path?;
+    ListService::new(auth_user.id).find(&state.db_pool, id).await?;
+    let Json(request) = request?;
+
+    create(
+        state,
+        auth_user,
+        TodoRequest {
+            list_id: Some(id),
+            ..request
+        },
+    )
+    .await
+}
+
+// Todos may only be attached to lists of their own owner. Runs on the
+// transaction that attaches the todo, which keeps the list from being deleted
+// until the todo is committed.
+async fn ensure_own_list(
+    conn: &mut PgConnection,
+    owner_id: i32,
+    list_id: Option<i32>,
+) -> Result<(), AppError> {
+    let Some(list_id) = list_id else {
+        return Ok(());
+    };
+    match ListService::new(owner_id).lock(conn, list_id).await {
+        Ok(()) => Ok(()),
+        Err(sqlx::Error::RowNotFound) => Err(AppError::Validation(vec![FieldViolation {
+            field: "list_id".to_string(),
+            reason: "must reference one of your lists".to_string(),
+        }])),
+        Err(error) => Err(error.into()),
     }
 }
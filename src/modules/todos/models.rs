@@ -1,8 +1,247 @@
+use std::fmt;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Deserialize;
+
 #[derive(Debug, sqlx::FromRow)]
 pub struct Todo {
     pub id: i32,
     pub title: String,
     pub content: String,
+    pub completed: bool,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub owner_id: Option<i32>,
+    pub list_id: Option<i32>,
+    pub position: i64,
+    pub version: i32,
+    pub deleted_at: Option<DateTime<Utc>>,
     #[sqlx(default)]
-    pub total: i64
-}
\ No newline at end of file
+    pub total: i64,
+    // Loaded separately by `service::load_details`.
+    #[sqlx(skip)]
+    pub tags: Vec<String>,
+    #[sqlx(skip)]
+    pub progress: Progress,
+}
+
+/// How many of the checklist items of a todo are done.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Progress {
+    pub done: i64,
+    pub total: i64,
+}
+
+#[derive(Debug)]
+pub struct TodoFields {
+    pub title: String,
+    pub content: String,
+    pub completed: bool,
+    pub due_at: Option<DateTime<Utc>>,
+    pub list_id: Option<i32>,
+}
+
+#[derive(Debug, Default)]
+pub struct TodoChanges {
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub completed: Option<bool>,
+    pub due_at: Option<Option<DateTime<Utc>>>,
+    pub list_id: Option<Option<i32>>,
+}
+
+impl TodoChanges {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.content.is_none()
+            && self.completed.is_none()
+            && self.due_at.is_none()
+            && self.list_id.is_none()
+    }
+}
+
+#[derive(Debug)]
+pub enum BulkOperation {
+    Create(TodoFields),
+    Update { id: i32, changes: TodoChanges },
+    Delete { id: i32 },
+}
+
+#[derive(Debug)]
+pub enum BulkOutcome {
+    Created(Todo),
+    Updated(Todo),
+    Deleted,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SortField {
+    #[default]
+    Position,
+    Id,
+    Title,
+    Completed,
+    DueAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl SortField {
+    pub const NAMES: &'static [&'static str] =
+        &["position", "id", "title", "completed", "due_at", "created_at", "updated_at"];
+
+    pub fn from_name(name: &str) -> Option<SortField> {
+        match name {
+            "position" => Some(SortField::Position),
+            "id" => Some(SortField::Id),
+            "title" => Some(SortField::Title),
+            "completed" => Some(SortField::Completed),
+            "due_at" => Some(SortField::DueAt),
+            "created_at" => Some(SortField::CreatedAt),
+            "updated_at" => Some(SortField::UpdatedAt),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SortField::Position => "position",
+            SortField::Id => "id",
+            SortField::Title => "title",
+            SortField::Completed => "completed",
+            SortField::DueAt => "due_at",
+            SortField::CreatedAt => "created_at",
+            SortField::UpdatedAt => "updated_at",
+        }
+    }
+
+    // Missing due dates sort last in both directions, so the ordering never
+    // has to compare NULLs and keyset pagination can use a plain row comparison.
+    pub fn expression(&self, direction: SortDirection) -> &'static str {
+        match (self, direction) {
+            (SortField::DueAt, SortDirection::Asc) => "COALESCE(due_at, 'infinity'::timestamptz)",
+            (SortField::DueAt, SortDirection::Desc) => "COALESCE(due_at, '-infinity'::timestamptz)",
+            (field, _) => field.name(),
+        }
+    }
+
+    pub fn sql_type(&self) -> &'static str {
+        match self {
+            SortField::Position => "bigint",
+            SortField::Id => "integer",
+            SortField::Title => "varchar",
+            SortField::Completed => "boolean",
+            SortField::DueAt | SortField::CreatedAt | SortField::UpdatedAt => "timestamptz",
+        }
+    }
+
+    pub fn value_of(&self, todo: &Todo, direction: SortDirection) -> String {
+        let timestamp = |value: &DateTime<Utc>| value.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+        match self {
+            SortField::Position => todo.position.to_string(),
+            SortField::Id => todo.id.to_string(),
+            SortField::Title => todo.title.clone(),
+            SortField::Completed => todo.completed.to_string(),
+            SortField::DueAt => match (&todo.due_at, direction) {
+                (Some(due_at), _) => timestamp(due_at),
+                (None, SortDirection::Asc) => "infinity".to_string(),
+                (None, SortDirection::Desc) => "-infinity".to_string(),
+            },
+            SortField::CreatedAt => timestamp(&todo.created_at),
+            SortField::UpdatedAt => timestamp(&todo.updated_at),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+
+    pub fn comparator(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => ">",
+            SortDirection::Desc => "<",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TodoSort {
+    pub field: SortField,
+    pub direction: SortDirection,
+}
+
+impl TodoSort {
+    pub fn parse(value: &str) -> Option<TodoSort> {
+        let (field, direction) = match value.split_once(':') {
+            None => (value, SortDirection::Asc),
+            Some((field, "asc")) => (field, SortDirection::Asc),
+            Some((field, "desc")) => (field, SortDirection::Desc),
+            Some(_) => return None,
+        };
+        SortField::from_name(field).map(|field| TodoSort { field, direction })
+    }
+
+    pub fn position_of(&self, todo: &Todo) -> KeysetPosition {
+        KeysetPosition {
+            value: self.field.value_of(todo, self.direction),
+            id: todo.id,
+        }
+    }
+}
+
+impl fmt::Display for TodoSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.direction {
+            SortDirection::Asc => "asc",
+            SortDirection::Desc => "desc",
+        };
+        write!(f, "{}:{}", self.field.name(), direction)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeysetPosition {
+    pub value: String,
+    pub id: i32,
+}
+
+/// Where a moved todo goes relative to the todo it is moved next to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Placement {
+    Before,
+    After,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMatch {
+    #[default]
+    Any,
+    All,
+}
+
+#[derive(Debug, Default)]
+pub struct TodoFilter {
+    pub search: Option<String>,
+    pub completed: Option<bool>,
+    pub due_before: Option<DateTime<Utc>>,
+    pub due_after: Option<DateTime<Utc>>,
+    pub list_id: Option<i32>,
+    pub tags: Vec<String>,
+    pub tag_match: TagMatch,
+    pub sort: TodoSort,
+}
@@ -1,68 +1,696 @@
-use std::sync::Arc;
+use std::{collections::HashMap, future::Future};
 
-use sqlx::{postgres::PgQueryResult, Postgres};
+use chrono::{DateTime, Utc};
 
-use crate::configs::state::AppState;
+use sqlx::{postgres::PgQueryResult, Acquire, Executor, PgConnection, Postgres, QueryBuilder};
 
 use super::models;
 
+// Distance between the positions of neighbouring todos when they are
+// appended or renumbered.
+const POSITION_GAP: i64 = 1024;
+
+// Appends the todo after the owner's last one.
+const INSERT_TODO: &str = "INSERT INTO todos \
+    (title, content, completed, completed_at, due_at, list_id, owner_id, position) \
+    VALUES ($1, $2, $3, CASE WHEN $3 THEN now() END, $4, $5, $6, \
+    (SELECT COALESCE(MAX(position), 0) + $7 FROM todos WHERE owner_id = $6)) RETURNING *;";
+
+const FIND_TODO: &str =
+    "SELECT * FROM todos WHERE id = $1 AND owner_id = $2 AND deleted_at IS NULL;";
+
+const SOFT_DELETE_TODO: &str = "UPDATE todos SET deleted_at = now() \
+    WHERE id = $1 AND owner_id = $2 AND deleted_at IS NULL;";
+
+/// Reads and writes the todos owned by a single user; todos of anyone else
+/// behave as if they did not exist.
+///
+/// Every method runs on the connection it is given: the pool, or the
+/// transaction of a `utils::unit_of_work` to make several calls atomic. The
+/// methods spell out that their futures are `Send`, as the compiler fails to
+/// prove it at the call site when they borrow a transaction.
 pub struct TodoService {
-    db_pool: sqlx::Pool<Postgres>,
+    owner_id: i32,
 }
 impl TodoService {
-    pub fn new(state: Arc<AppState>) -> Self {
-        Self {
-            db_pool: state.db_pool.clone(),
+    pub fn new(owner_id: i32) -> Self {
+        Self { owner_id }
+    }
+
+    pub fn find<'a, 'c, A>(
+        &'a self,
+        db: A,
+        id: i32,
+    ) -> impl Future<Output = Result<models::Todo, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            let mut todo = sqlx::query_as::<_, models::Todo>(FIND_TODO)
+                .bind(id)
+                .bind(self.owner_id)
+                .fetch_one(&mut *conn)
+                .await?;
+            load_details(&mut conn, std::slice::from_mut(&mut todo)).await?;
+            Ok(todo)
         }
     }
 
-    pub async fn find(&self, id: i32) -> Result<models::Todo, sqlx::Error> {
-        sqlx::query_as::<_, models::Todo>("SELECT * FROM todos WHERE id = $1;")
+    // Like `find`, but also locks the todo until the end of the transaction,
+    // so that it cannot change between reading and writing it.
+    pub fn find_for_update<'a, 'c, A>(
+        &'a self,
+        db: A,
+        id: i32,
+    ) -> impl Future<Output = Result<models::Todo, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            let mut todo = sqlx::query_as::<_, models::Todo>(
+                "SELECT * FROM todos WHERE id = $1 AND owner_id = $2 AND deleted_at IS NULL \
+                FOR UPDATE;",
+            )
+            .bind(id)
+            .bind(self.owner_id)
+            .fetch_one(&mut *conn)
+            .await?;
+            load_details(&mut conn, std::slice::from_mut(&mut todo)).await?;
+            Ok(todo)
+        }
+    }
+
+    // The current version of the todo, for the `ETag` of changes to its tags
+    // or checklist, which bump it without returning the todo.
+    pub fn version<'a, 'c, A>(
+        &'a self,
+        db: A,
+        id: i32,
+    ) -> impl Future<Output = Result<i32, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            sqlx::query_scalar::<_, i32>(
+                "SELECT version FROM todos WHERE id = $1 AND owner_id = $2 AND deleted_at IS NULL;",
+            )
             .bind(id)
-            .fetch_one(&self.db_pool)
+            .bind(self.owner_id)
+            .fetch_one(&mut *conn)
             .await
+        }
     }
 
-    pub async fn create(&self, title: &str, content: &str) -> Result<models::Todo, sqlx::Error> {
-        sqlx::query_as::<_, models::Todo>(
-            "INSERT INTO todos (title, content) VALUES ($1, $2) RETURNING *;",
-        )
-        .bind(title)
-        .bind(content)
-        .fetch_one(&self.db_pool)
-        .await
+    pub fn create<'a, 'c, A>(
+        &'a self,
+        db: A,
+        fields: &'a models::TodoFields,
+    ) -> impl Future<Output = Result<models::Todo, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            insert(&mut conn, self.owner_id, fields).await
+        }
     }
 
-    pub async fn list(&self, limit: i64, offset: i64) -> Result<Vec<models::Todo>, sqlx::Error> {
-        sqlx::query_as::<_, models::Todo>(
-            "SELECT * , COUNT(*) OVER () AS total FROM todos LIMIT $1 OFFSET $2;",
+    pub fn list<'a, 'c, A>(
+        &'a self,
+        db: A,
+        filter: &'a models::TodoFilter,
+        limit: i64,
+        offset: i64,
+    ) -> impl Future<Output = Result<Vec<models::Todo>, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut query = QueryBuilder::<Postgres>::new(
+                "SELECT * , COUNT(*) OVER () AS total FROM todos WHERE owner_id = ",
+            );
+            query.push_bind(self.owner_id);
+            push_filter(&mut query, filter);
+            push_order(&mut query, &filter.sort);
+            query
+                .push(" LIMIT ")
+                .push_bind(limit)
+                .push(" OFFSET ")
+                .push_bind(offset)
+                .push(";");
+
+            let mut conn = db.acquire().await?;
+            let mut todos = query
+                .build_query_as::<models::Todo>()
+                .fetch_all(&mut *conn)
+                .await?;
+            load_details(&mut conn, &mut todos).await?;
+            Ok(todos)
+        }
+    }
+
+    pub fn list_page<'a, 'c, A>(
+        &'a self,
+        db: A,
+        filter: &'a models::TodoFilter,
+        limit: i64,
+        offset: i64,
+        after: Option<&'a models::KeysetPosition>,
+    ) -> impl Future<Output = Result<Vec<models::Todo>, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM todos WHERE owner_id = ");
+            query.push_bind(self.owner_id);
+            push_filter(&mut query, filter);
+            if let Some(after) = after {
+                let sort = &filter.sort;
+                query
+                    .push(format!(
+                        " AND ({}, id) {} (CAST(",
+                        sort.field.expression(sort.direction),
+                        sort.direction.comparator()
+                    ))
+                    .push_bind(after.value.clone())
+                    .push(format!(" AS {}), ", sort.field.sql_type()))
+                    .push_bind(after.id)
+                    .push(")");
+            }
+            push_order(&mut query, &filter.sort);
+            query
+                .push(" LIMIT ")
+                .push_bind(limit)
+                .push(" OFFSET ")
+                .push_bind(offset)
+                .push(";");
+
+            let mut conn = db.acquire().await?;
+            let mut todos = query
+                .build_query_as::<models::Todo>()
+                .fetch_all(&mut *conn)
+                .await?;
+            load_details(&mut conn, &mut todos).await?;
+            Ok(todos)
+        }
+    }
+
+    pub fn count<'a, 'c, A>(
+        &'a self,
+        db: A,
+        filter: &'a models::TodoFilter,
+    ) -> impl Future<Output = Result<i64, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut query =
+                QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM todos WHERE owner_id = ");
+            query.push_bind(self.owner_id);
+            push_filter(&mut query, filter);
+            query.push(";");
+
+            let mut conn = db.acquire().await?;
+            query
+                .build_query_scalar::<i64>()
+                .fetch_one(&mut *conn)
+                .await
+        }
+    }
+
+    pub fn update<'a, 'c, A>(
+        &'a self,
+        db: A,
+        id: i32,
+        fields: &'a models::TodoFields,
+    ) -> impl Future<Output = Result<models::Todo, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            let mut todo = sqlx::query_as::<_, models::Todo>(
+                "UPDATE todos SET title = $1, content = $2, completed = $3, \
+                completed_at = CASE WHEN $3 THEN COALESCE(completed_at, now()) END, \
+                due_at = $4, list_id = $5, version = version + 1, updated_at = now() \
+                WHERE id = $6 AND owner_id = $7 AND deleted_at IS NULL RETURNING * ;",
+            )
+            .bind(&fields.title)
+            .bind(&fields.content)
+            .bind(fields.completed)
+            .bind(fields.due_at)
+            .bind(fields.list_id)
+            .bind(id)
+            .bind(self.owner_id)
+            .fetch_one(&mut *conn)
+            .await?;
+            load_details(&mut conn, std::slice::from_mut(&mut todo)).await?;
+            Ok(todo)
+        }
+    }
+
+    pub fn patch<'a, 'c, A>(
+        &'a self,
+        db: A,
+        id: i32,
+        changes: &'a models::TodoChanges,
+    ) -> impl Future<Output = Result<models::Todo, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            let mut todo = patch(&mut conn, self.owner_id, id, changes).await?;
+            load_details(&mut conn, std::slice::from_mut(&mut todo)).await?;
+            Ok(todo)
+        }
+    }
+
+    // Moves the todo right before or after `target_id` by taking the midpoint
+    // of the positions around the gap. Only when there is no room left are
+    // the owner's todos renumbered. Concurrent moves may end up on the same
+    // position, which the `id` tie-breaker of every ordering keeps stable.
+    pub fn move_to<'a, 'c, A>(
+        &'a self,
+        db: A,
+        id: i32,
+        target_id: i32,
+        placement: models::Placement,
+    ) -> impl Future<Output = Result<models::Todo, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            // A savepoint when `db` is a transaction already.
+            let mut transaction = db.begin().await?;
+            let target = sqlx::query_scalar::<_, i64>(
+                "SELECT position FROM todos WHERE id = $1 AND owner_id = $2 AND deleted_at IS NULL;",
+            )
+            .bind(target_id)
+            .bind(self.owner_id)
+            .fetch_one(&mut *transaction)
+            .await?;
+            let (comparator, direction) = match placement {
+                models::Placement::Before => ("<", "DESC"),
+                models::Placement::After => (">", "ASC"),
+            };
+            let neighbour = sqlx::query_scalar::<_, i64>(&format!(
+                "SELECT position FROM todos WHERE owner_id = $1 AND id <> $2 \
+                AND deleted_at IS NULL AND (position, id) {} ($3, $4) ORDER BY position {}, id {} LIMIT 1;",
+                comparator, direction, direction
+            ))
+            .bind(self.owner_id)
+            .bind(id)
+            .bind(target)
+            .bind(target_id)
+            .fetch_optional(&mut *transaction)
+            .await?;
+
+            let position = match (neighbour, placement) {
+                (None, models::Placement::Before) => Some(target - POSITION_GAP),
+                (None, models::Placement::After) => Some(target + POSITION_GAP),
+                (Some(neighbour), _) if (target - neighbour).abs() >= 2 => {
+                    Some(target + (neighbour - target) / 2)
+                }
+                (Some(_), _) => None,
+            };
+            let position = match position {
+                Some(position) => position,
+                None => {
+                    self.renumber(&mut transaction).await?;
+                    let target = sqlx::query_scalar::<_, i64>("SELECT position FROM todos WHERE id = $1;")
+                        .bind(target_id)
+                        .fetch_one(&mut *transaction)
+                        .await?;
+                    // Renumbering left a full gap on both sides of the target.
+                    match placement {
+                        models::Placement::Before => target - POSITION_GAP / 2,
+                        models::Placement::After => target + POSITION_GAP / 2,
+                    }
+                }
+            };
+
+            let mut todo = sqlx::query_as::<_, models::Todo>(
+                "UPDATE todos SET position = $1, version = version + 1, updated_at = now() \
+                WHERE id = $2 AND owner_id = $3 AND deleted_at IS NULL RETURNING *;",
+            )
+            .bind(position)
+            .bind(id)
+            .bind(self.owner_id)
+            .fetch_one(&mut *transaction)
+            .await?;
+            load_details(&mut transaction, std::slice::from_mut(&mut todo)).await?;
+            transaction.commit().await?;
+            Ok(todo)
+        }
+    }
+
+    async fn renumber(
+        &self,
+        transaction: &mut sqlx::Transaction<'_, Postgres>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE todos SET position = ranked.rank * $1 \
+            FROM (SELECT id, ROW_NUMBER() OVER (ORDER BY position, id) AS rank \
+            FROM todos WHERE owner_id = $2) AS ranked WHERE todos.id = ranked.id;",
         )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.db_pool)
-        .await
+        .bind(POSITION_GAP)
+        .bind(self.owner_id)
+        .execute(&mut **transaction)
+        .await?;
+        Ok(())
     }
 
-    pub async fn update(
+    // Moves the todo to the trash, from where it can be restored until it is
+    // purged by `purge_trash`.
+    pub fn delete<'a, 'c, A>(
+        &'a self,
+        db: A,
+        id: i32,
+    ) -> impl Future<Output = Result<PgQueryResult, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            sqlx::query(SOFT_DELETE_TODO)
+                .bind(id)
+                .bind(self.owner_id)
+                .execute(&mut *conn)
+                .await
+        }
+    }
+
+    /// Applies `operations` in order within one transaction. Unless `partial`
+    /// is set, the first failing operation rolls back the whole batch and is
+    /// the last outcome returned; with `partial`, every operation runs behind
+    /// its own savepoint and only the failed ones are undone.
+    pub fn bulk<'a, 'c, A>(
+        &'a self,
+        db: A,
+        operations: &'a [models::BulkOperation],
+        partial: bool,
+    ) -> impl Future<Output = Result<Vec<Result<models::BulkOutcome, sqlx::Error>>, sqlx::Error>>
+        + Send
+        + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut transaction = db.begin().await?;
+            let mut outcomes = Vec::with_capacity(operations.len());
+            for operation in operations.iter() {
+                let outcome = match partial {
+                    true => {
+                        let mut savepoint = transaction.begin().await?;
+                        let outcome = self.apply(&mut savepoint, operation).await;
+                        match outcome {
+                            Ok(_) => savepoint.commit().await?,
+                            Err(_) => savepoint.rollback().await?,
+                        }
+                        outcome
+                    }
+                    false => self.apply(&mut transaction, operation).await,
+                };
+                if outcome.is_err() && !partial {
+                    transaction.rollback().await?;
+                    outcomes.push(outcome);
+                    return Ok(outcomes);
+                }
+                outcomes.push(outcome);
+            }
+
+            for outcome in outcomes.iter_mut() {
+                if let Ok(models::BulkOutcome::Created(todo) | models::BulkOutcome::Updated(todo)) =
+                    outcome
+                {
+                    load_details(&mut transaction, std::slice::from_mut(todo)).await?;
+                }
+            }
+            transaction.commit().await?;
+            Ok(outcomes)
+        }
+    }
+
+    async fn apply(
         &self,
+        conn: &mut PgConnection,
+        operation: &models::BulkOperation,
+    ) -> Result<models::BulkOutcome, sqlx::Error> {
+        match operation {
+            models::BulkOperation::Create(fields) => insert(conn, self.owner_id, fields)
+                .await
+                .map(models::BulkOutcome::Created),
+            models::BulkOperation::Update { id, changes } => {
+                patch(conn, self.owner_id, *id, changes)
+                    .await
+                    .map(models::BulkOutcome::Updated)
+            }
+            models::BulkOperation::Delete { id } => {
+                let result = sqlx::query(SOFT_DELETE_TODO)
+                    .bind(id)
+                    .bind(self.owner_id)
+                    .execute(conn)
+                    .await?;
+                match result.rows_affected() {
+                    0 => Err(sqlx::Error::RowNotFound),
+                    _ => Ok(models::BulkOutcome::Deleted),
+                }
+            }
+        }
+    }
+
+    pub fn list_trash<'a, 'c, A>(
+        &'a self,
+        db: A,
+        limit: i64,
+        offset: i64,
+    ) -> impl Future<Output = Result<Vec<models::Todo>, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            let mut todos = sqlx::query_as::<_, models::Todo>(
+                "SELECT *, COUNT(*) OVER () AS total FROM todos \
+                WHERE owner_id = $1 AND deleted_at IS NOT NULL \
+                ORDER BY deleted_at DESC, id DESC LIMIT $2 OFFSET $3;",
+            )
+            .bind(self.owner_id)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&mut *conn)
+            .await?;
+            load_details(&mut conn, &mut todos).await?;
+            Ok(todos)
+        }
+    }
+
+    pub fn restore<'a, 'c, A>(
+        &'a self,
+        db: A,
         id: i32,
-        title: &str,
-        content: &str,
-    ) -> Result<models::Todo, sqlx::Error> {
-        sqlx::query_as::<_, models::Todo>(
-            "UPDATE todos SET title = $1, content = $2 WHERE id = $3 RETURNING * ;",
-        )
-        .bind(title)
-        .bind(content)
-        .bind(id)
-        .fetch_one(&self.db_pool)
+    ) -> impl Future<Output = Result<models::Todo, sqlx::Error>> + Send + 'a
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'a,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            let mut todo = sqlx::query_as::<_, models::Todo>(
+                "UPDATE todos SET deleted_at = NULL, version = version + 1, updated_at = now() \
+                WHERE id = $1 AND owner_id = $2 AND deleted_at IS NOT NULL RETURNING *;",
+            )
+            .bind(id)
+            .bind(self.owner_id)
+            .fetch_one(&mut *conn)
+            .await?;
+            load_details(&mut conn, std::slice::from_mut(&mut todo)).await?;
+            Ok(todo)
+        }
+    }
+}
+
+/// Permanently deletes the todos of every user that were trashed before
+/// `deleted_before`, along with their items and tags.
+pub async fn purge_trash<'c, E>(db: E, deleted_before: DateTime<Utc>) -> Result<u64, sqlx::Error>
+where
+    E: Executor<'c, Database = Postgres>,
+{
+    sqlx::query("DELETE FROM todos WHERE deleted_at < $1;")
+        .bind(deleted_before)
+        .execute(db)
         .await
+        .map(|result| result.rows_affected())
+}
+
+/// Bumps the version of the todo, so that its `ETag` changes along with the
+/// tags or checklist that are stored outside of the `todos` table.
+pub async fn touch(conn: &mut PgConnection, id: i32) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE todos SET version = version + 1, updated_at = now() WHERE id = $1;")
+        .bind(id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// Fills in the tags and checklist progress of `todos` with one query each,
+/// however many todos there are.
+pub async fn load_details(
+    conn: &mut PgConnection,
+    todos: &mut [models::Todo],
+) -> Result<(), sqlx::Error> {
+    if todos.is_empty() {
+        return Ok(());
+    }
+    let ids: Vec<i32> = todos.iter().map(|todo| todo.id).collect();
+    let tag_rows = sqlx::query_as::<_, (i32, String)>(
+        "SELECT todo_tags.todo_id, tags.name FROM todo_tags \
+        JOIN tags ON tags.id = todo_tags.tag_id \
+        WHERE todo_tags.todo_id = ANY($1) ORDER BY tags.name;",
+    )
+    .bind(&ids)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut tags: HashMap<i32, Vec<String>> = HashMap::new();
+    for (todo_id, name) in tag_rows {
+        tags.entry(todo_id).or_default().push(name);
     }
+    let progress: HashMap<i32, models::Progress> = sqlx::query_as::<_, (i32, i64, i64)>(
+        "SELECT todo_id, COUNT(*) FILTER (WHERE done), COUNT(*) FROM todo_items \
+        WHERE todo_id = ANY($1) GROUP BY todo_id;",
+    )
+    .bind(&ids)
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|(todo_id, done, total)| (todo_id, models::Progress { done, total }))
+    .collect();
 
-    pub async fn delete(&self, id: i32) -> Result<PgQueryResult, sqlx::Error> {
-        sqlx::query("DELETE FROM todos WHERE id = $1;")
+    for todo in todos.iter_mut() {
+        todo.tags = tags.remove(&todo.id).unwrap_or_default();
+        todo.progress = progress.get(&todo.id).copied().unwrap_or_default();
+    }
+    Ok(())
+}
+
+async fn insert(
+    conn: &mut PgConnection,
+    owner_id: i32,
+    fields: &models::TodoFields,
+) -> Result<models::Todo, sqlx::Error> {
+    sqlx::query_as::<_, models::Todo>(INSERT_TODO)
+        .bind(&fields.title)
+        .bind(&fields.content)
+        .bind(fields.completed)
+        .bind(fields.due_at)
+        .bind(fields.list_id)
+        .bind(owner_id)
+        .bind(POSITION_GAP)
+        .fetch_one(conn)
+        .await
+}
+
+// An empty patch changes nothing, not even the version, and returns the todo
+// as it is.
+async fn patch(
+    conn: &mut PgConnection,
+    owner_id: i32,
+    id: i32,
+    changes: &models::TodoChanges,
+) -> Result<models::Todo, sqlx::Error> {
+    if changes.is_empty() {
+        return sqlx::query_as::<_, models::Todo>(FIND_TODO)
             .bind(id)
-            .execute(&self.db_pool)
-            .await
+            .bind(owner_id)
+            .fetch_one(conn)
+            .await;
+    }
+    build_patch(id, owner_id, changes)
+        .build_query_as::<models::Todo>()
+        .fetch_one(conn)
+        .await
+}
+
+fn build_patch<'a>(
+    id: i32,
+    owner_id: i32,
+    changes: &'a models::TodoChanges,
+) -> QueryBuilder<'a, Postgres> {
+    let mut query =
+        QueryBuilder::<Postgres>::new("UPDATE todos SET version = version + 1, updated_at = now()");
+    if let Some(title) = &changes.title {
+        query.push(", title = ").push_bind(title);
+    }
+    if let Some(content) = &changes.content {
+        query.push(", content = ").push_bind(content);
+    }
+    if let Some(completed) = changes.completed {
+        query
+            .push(", completed = ")
+            .push_bind(completed)
+            .push(", completed_at = CASE WHEN ")
+            .push_bind(completed)
+            .push(" THEN COALESCE(completed_at, now()) END");
     }
+    if let Some(due_at) = changes.due_at {
+        query.push(", due_at = ").push_bind(due_at);
+    }
+    if let Some(list_id) = changes.list_id {
+        query.push(", list_id = ").push_bind(list_id);
+    }
+    query
+        .push(" WHERE id = ")
+        .push_bind(id)
+        .push(" AND owner_id = ")
+        .push_bind(owner_id)
+        .push(" AND deleted_at IS NULL RETURNING *;");
+    query
+}
+
+fn push_filter(query: &mut QueryBuilder<Postgres>, filter: &models::TodoFilter) {
+    // Trashed todos are only reachable through `list_trash` and `restore`.
+    query.push(" AND deleted_at IS NULL");
+    if let Some(search) = &filter.search {
+        query
+            .push(" AND search @@ websearch_to_tsquery('english', ")
+            .push_bind(search.clone())
+            .push(")");
+    }
+    if let Some(completed) = filter.completed {
+        query.push(" AND completed = ").push_bind(completed);
+    }
+    if let Some(due_before) = filter.due_before {
+        query.push(" AND due_at < ").push_bind(due_before);
+    }
+    if let Some(due_after) = filter.due_after {
+        query.push(" AND due_at > ").push_bind(due_after);
+    }
+    if let Some(list_id) = filter.list_id {
+        query.push(" AND list_id = ").push_bind(list_id);
+    }
+    if !filter.tags.is_empty() {
+        let matching_tags = "SELECT COUNT(*) FROM todo_tags JOIN tags ON tags.id = todo_tags.tag_id \
+            WHERE todo_tags.todo_id = todos.id AND tags.name = ANY(";
+        query
+            .push(format!(" AND ({}", matching_tags))
+            .push_bind(filter.tags.clone());
+        match filter.tag_match {
+            models::TagMatch::Any => query.push(")) > 0"),
+            models::TagMatch::All => query.push(")) = ").push_bind(filter.tags.len() as i64),
+        };
+    }
+}
+
+fn push_order(query: &mut QueryBuilder<Postgres>, sort: &models::TodoSort) {
+    let direction = sort.direction.keyword();
+    query.push(format!(
+        " ORDER BY {} {}, id {}",
+        sort.field.expression(sort.direction),
+        direction,
+        direction
+    ));
 }
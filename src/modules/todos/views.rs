@@ -1,8 +1,102 @@
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 
+use super::models;
+use crate::views::errors::FieldViolation;
+
+#[derive(Serialize)]
+pub struct Progress {
+    pub done: i64,
+    pub total: i64,
+}
+
 #[derive(Serialize)]
 pub struct Todo {
     pub id: i32,
     pub title: String,
     pub content: String,
+    pub completed: bool,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub list_id: Option<i32>,
+    pub position: i64,
+    pub tags: Vec<String>,
+    pub progress: Progress,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<models::Todo> for Todo {
+    fn from(todo: models::Todo) -> Self {
+        Todo {
+            id: todo.id,
+            title: todo.title,
+            content: todo.content,
+            completed: todo.completed,
+            completed_at: todo.completed_at,
+            due_at: todo.due_at,
+            list_id: todo.list_id,
+            position: todo.position,
+            tags: todo.tags,
+            progress: Progress {
+                done: todo.progress.done,
+                total: todo.progress.total,
+            },
+            created_at: todo.created_at,
+            updated_at: todo.updated_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TrashedTodo {
+    #[serde(flatten)]
+    pub todo: Todo,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl From<models::Todo> for TrashedTodo {
+    fn from(todo: models::Todo) -> Self {
+        TrashedTodo {
+            deleted_at: todo.deleted_at,
+            todo: Todo::from(todo),
+        }
+    }
+}
+
+/// The outcome of one operation of a bulk request.
+#[derive(Serialize)]
+pub struct BulkResult {
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub todo: Option<Todo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldViolation>,
+}
+
+impl From<models::BulkOutcome> for BulkResult {
+    fn from(outcome: models::BulkOutcome) -> Self {
+        let (status, todo) = match outcome {
+            models::BulkOutcome::Created(todo) => (StatusCode::CREATED, Some(Todo::from(todo))),
+            models::BulkOutcome::Updated(todo) => (StatusCode::OK, Some(Todo::from(todo))),
+            models::BulkOutcome::Deleted => (StatusCode::NO_CONTENT, None),
+        };
+        BulkResult {
+            status: status.as_u16(),
+            todo,
+            message: None,
+            errors: Vec::new(),
+        }
+    }
+}
+
+/// The results of a bulk request, in the order of its operations. Nothing
+/// was changed unless `committed` is set.
+#[derive(Serialize)]
+pub struct BulkResponse {
+    pub committed: bool,
+    pub results: Vec<BulkResult>,
 }
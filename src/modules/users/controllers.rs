@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use super::{models, service::UserService, views};
+use crate::configs::state::AppState;
+use crate::utils::auth::AuthUser;
+use crate::utils::error::AppError;
+use crate::utils::password;
+use crate::utils::validation::{is_email, Rule, Validate, Validator};
+use axum::{
+    extract::{rejection::JsonRejection, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct RegisterRequest {
+    email: String,
+    password: String,
+}
+
+const EMAIL_RULES: &[Rule] = &[
+    Rule::Required,
+    Rule::MaxLength(255),
+    Rule::Format(is_email, "a valid email address"),
+];
+
+const PASSWORD_RULES: &[Rule] = &[Rule::Required, Rule::MinLength(8), Rule::MaxLength(128)];
+
+impl Validate for RegisterRequest {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .field("email", Some(self.email.trim()), EMAIL_RULES)
+            .field("password", Some(&self.password), PASSWORD_RULES)
+            .finish()
+    }
+}
+
+pub async fn register(
+    State(state): State<Arc<AppState>>,
+    request: Result<Json<RegisterRequest>, JsonRejection>,
+) -> Result<(StatusCode, Json<views::User>), AppError> {
+    let Json(request) = request?;
+    request.validate()?;
+
+    let fields = models::UserFields {
+        email: models::normalize_email(&request.email),
+        password_hash: password::hash(request.password).await?,
+    };
+    let user_service = UserService::new(state);
+    let user = user_service.create(&fields).await?;
+
+    Ok((StatusCode::CREATED, Json(views::User::from(user))))
+}
+
+pub async fn me(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+) -> Result<(StatusCode, Json<views::User>), AppError> {
+    let user_service = UserService::new(state);
+    let user = user_service.find(auth_user.id).await?;
+
+    Ok((StatusCode::OK, Json(views::User::from(user))))
+}
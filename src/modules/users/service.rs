@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use sqlx::Postgres;
+
+use crate::configs::state::AppState;
+
+use super::models;
+
+pub struct UserService {
+    db_pool: sqlx::Pool<Postgres>,
+}
+impl UserService {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self {
+            db_pool: state.db_pool.clone(),
+        }
+    }
+
+    pub async fn find(&self, id: i32) -> Result<models::User, sqlx::Error> {
+        sqlx::query_as::<_, models::User>("SELECT * FROM users WHERE id = $1;")
+            .bind(id)
+            .fetch_one(&self.db_pool)
+            .await
+    }
+
+    pub async fn find_by_email(&self, email: &str) -> Result<models::User, sqlx::Error> {
+        sqlx::query_as::<_, models::User>("SELECT * FROM users WHERE email = $1;")
+            .bind(email)
+            .fetch_one(&self.db_pool)
+            .await
+    }
+
+    pub async fn create(&self, fields: &models::UserFields) -> Result<models::User, sqlx::Error> {
+        sqlx::query_as::<_, models::User>(
+            "INSERT INTO users (email, password_hash) VALUES ($1, $2) RETURNING *;",
+        )
+        .bind(&fields.email)
+        .bind(&fields.password_hash)
+        .fetch_one(&self.db_pool)
+        .await
+    }
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::models;
+
+#[derive(Serialize)]
+pub struct User {
+    pub id: i32,
+    pub email: String,
+    pub role: models::Role,
+    pub disabled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<models::User> for User {
+    fn from(user: models::User) -> Self {
+        User {
+            id: user.id,
+            email: user.email,
+            role: user.role,
+            disabled_at: user.disabled_at,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
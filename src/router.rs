@@ -1,17 +1,103 @@
 use std::sync::Arc;
 
-use crate::modules::todos;
+use crate::modules::{admin, api_keys, auth, health, lists, tags, todo_items, todos, users};
 use crate::configs::state;
+use crate::utils::{app, idempotency};
 use axum::{
-    routing::{delete, get, post, put},
+    middleware,
+    routing::{delete, get, patch, post, put},
     Router,
 };
 
+pub fn modules() -> Vec<app::Module<state::AppState>> {
+    vec![
+        ("/", health_router),
+        ("/auth", auth_router),
+        ("/todos", todos_router),
+        ("/lists", lists_router),
+        ("/tags", tags_router),
+        ("/users", users_router),
+        ("/api-keys", api_keys_router),
+        ("/admin", admin_router),
+    ]
+}
+
+// The whole application as served by `main`, so that tests go through the same
+// routes and middleware.
+pub fn app_router(state: Arc<state::AppState>) -> Router {
+    app::build_router(modules())
+        .layer(middleware::from_fn_with_state(state.clone(), idempotency::replay))
+        .with_state(state)
+}
+
 pub fn todos_router() -> Router<Arc<state::AppState>> {
     Router::new()
+        .route("/trash", get(todos::controllers::trash))
+        .route("/bulk", post(todos::controllers::bulk))
         .route("/:id", get(todos::controllers::get))
         .route("/", get(todos::controllers::list))
         .route("/", post(todos::controllers::post))
         .route("/:id", put(todos::controllers::put))
+        .route("/:id", patch(todos::controllers::patch))
         .route("/:id", delete(todos::controllers::delete))
+        .route("/:id/move", post(todos::controllers::move_to))
+        .route("/:id/restore", post(todos::controllers::restore))
+        .route("/:id/tags", post(tags::controllers::add_to_todo))
+        .route("/:id/tags/:name", delete(tags::controllers::remove_from_todo))
+        .route("/:id/items", get(todo_items::controllers::list))
+        .route("/:id/items", post(todo_items::controllers::post))
+        .route("/:id/items", put(todo_items::controllers::reorder))
+        .route("/:id/items/:item_id", patch(todo_items::controllers::patch))
+        .route("/:id/items/:item_id", delete(todo_items::controllers::delete))
+}
+
+pub fn health_router() -> Router<Arc<state::AppState>> {
+    Router::new()
+        .route("/healthz", get(health::controllers::liveness))
+        .route("/readyz", get(health::controllers::readiness))
+}
+
+pub fn users_router() -> Router<Arc<state::AppState>> {
+    Router::new()
+        .route("/", post(users::controllers::register))
+        .route("/me", get(users::controllers::me))
+}
+
+pub fn auth_router() -> Router<Arc<state::AppState>> {
+    Router::new()
+        .route("/login", post(auth::controllers::login))
+        .route("/refresh", post(auth::controllers::refresh))
+        .route("/logout", post(auth::controllers::logout))
+}
+
+pub fn api_keys_router() -> Router<Arc<state::AppState>> {
+    Router::new()
+        .route("/", post(api_keys::controllers::post))
+        .route("/", get(api_keys::controllers::list))
+        .route("/:id", patch(api_keys::controllers::patch))
+        .route("/:id", delete(api_keys::controllers::delete))
+}
+
+
+pub fn admin_router() -> Router<Arc<state::AppState>> {
+    Router::new()
+        .route("/users", get(admin::controllers::list_users))
+        .route("/users/:id/disable", post(admin::controllers::disable_user))
+        .route("/users/:id/enable", post(admin::controllers::enable_user))
+        .route("/todos/:id", get(admin::controllers::get_todo))
+}
+
+pub fn lists_router() -> Router<Arc<state::AppState>> {
+    Router::new()
+        .route("/:id", get(lists::controllers::get))
+        .route("/", get(lists::controllers::list))
+        .route("/", post(lists::controllers::post))
+        .route("/:id", patch(lists::controllers::patch))
+        .route("/:id", delete(lists::controllers::delete))
+        .route("/:id/todos", get(todos::controllers::list_in_list))
+        .route("/:id/todos", post(todos::controllers::post_in_list))
 }
+
+pub fn tags_router() -> Router<Arc<state::AppState>> {
+    Router::new().route("/", get(tags::controllers::list))
+}
\ No newline at end of file
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use crate::configs::migrations::*;
+    use sqlx::{migrate::MigrateError, PgPool};
+
+    #[sqlx::test(migrations = false)]
+    async fn status_pending_ok(pool: PgPool) {
+        match status(&pool).await {
+            Err(error) => panic!("{}", error),
+            Ok(migrations) => {
+                assert!(!migrations.is_empty());
+                assert!(migrations.iter().all(|migration| !migration.applied));
+            }
+        }
+    }
+
+    #[sqlx::test(migrations = false)]
+    async fn run_and_revert_ok(pool: PgPool) {
+        if let Err(error) = run(&pool).await {
+            panic!("{}", error)
+        }
+        let migrations = status(&pool).await.unwrap();
+        assert!(migrations.iter().all(|migration| migration.applied));
+
+        let last = migrations.last().unwrap().version;
+        assert_eq!(revert_last(&pool).await.unwrap(), Some(last));
+        let migrations = status(&pool).await.unwrap();
+        assert!(!migrations.last().unwrap().applied);
+    }
+
+    #[sqlx::test(migrations = false)]
+    async fn revert_last_nothing_applied_ok(pool: PgPool) {
+        assert_eq!(revert_last(&pool).await.unwrap(), None);
+    }
+
+    #[sqlx::test]
+    async fn run_err_schema_ahead(pool: PgPool) {
+        sqlx::query(
+            "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time) VALUES (99990101000000, 'from the future', TRUE, '\\x00', 0);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        match run(&pool).await {
+            Err(MigrateError::VersionMissing(version)) => assert_eq!(version, 99990101000000),
+            result => panic!("expected: schema ahead error, got: {:?}", result),
+        }
+        assert_eq!(
+            describe_error(MigrateError::VersionMissing(99990101000000)),
+            "database schema is ahead of this binary: migration 99990101000000 is applied but unknown to this build"
+        );
+    }
+}
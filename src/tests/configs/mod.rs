@@ -1,2 +1,5 @@
 mod db;
+mod migrations;
+mod pagination;
+mod settings;
 mod state;
\ No newline at end of file
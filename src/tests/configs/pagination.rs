@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::configs::pagination::*;
+
+    #[test]
+    fn resolve_ok() {
+        let limits = PageSizeLimits::new(20, 50).unwrap();
+        assert_eq!(limits.resolve(None), (20, None));
+        assert_eq!(limits.resolve(Some(30)), (30, None));
+        assert_eq!(limits.resolve(Some(500)), (50, Some(500)));
+    }
+
+    #[test]
+    fn resolve_query_ok() {
+        let limits = PageSizeLimits::new(20, 50).unwrap();
+        assert_eq!(limits.resolve_query(None, None).unwrap(), (20, None));
+        assert_eq!(limits.resolve_query(Some(500), Some(0)).unwrap(), (50, Some(500)));
+    }
+
+    #[test]
+    fn resolve_query_err_out_of_range() {
+        let limits = PageSizeLimits::new(20, 50).unwrap();
+        assert!(limits.resolve_query(Some(0), None).is_err());
+        assert!(limits.resolve_query(None, Some(-1)).is_err());
+    }
+
+    #[test]
+    fn new_err_invalid_limits() {
+        assert!(PageSizeLimits::new(0, 10).is_err());
+        assert!(PageSizeLimits::new(20, 10).is_err());
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn from_sources_default_ok() {
+        let config = PaginationConfig::from_sources(&PaginationFile::default(), &vars(&[])).unwrap();
+        assert_eq!(config.for_module("todos"), PageSizeLimits::default());
+    }
+
+    #[test]
+    fn from_sources_per_module_ok() {
+        let file: PaginationFile = toml::from_str(
+            "default = 5\nmax = 20\n[modules.todos]\ndefault = 8\nmax = 30\n[modules.lists]\nmax = 40\n",
+        )
+        .unwrap();
+        let config = PaginationConfig::from_sources(
+            &file,
+            &vars(&[("TODOS_PAGE_SIZE_MAX", "100"), ("HOST", "localhost")]),
+        )
+        .unwrap();
+        assert_eq!(config.for_module("todos"), PageSizeLimits::new(8, 100).unwrap());
+        assert_eq!(config.for_module("lists"), PageSizeLimits::new(5, 40).unwrap());
+        assert_eq!(config.for_module("others"), PageSizeLimits::new(5, 20).unwrap());
+    }
+
+    #[test]
+    fn from_sources_err_invalid_values() {
+        let errors = PaginationConfig::from_sources(
+            &PaginationFile::default(),
+            &vars(&[("TODOS_PAGE_SIZE_MAX", "lots"), ("PAGE_SIZE_DEFAULT", "20")]),
+        )
+        .unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.contains(&"TODOS_PAGE_SIZE_MAX must be an integer, got \"lots\"".to_string()));
+        assert!(errors.contains(
+            &"pagination: default page size 20 is greater than max page size 10".to_string()
+        ));
+    }
+}
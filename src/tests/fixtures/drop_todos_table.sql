@@ -1 +1 @@
-DROP TABLE IF EXISTS todos;
\ No newline at end of file
+DROP TABLE IF EXISTS todos CASCADE;
\ No newline at end of file
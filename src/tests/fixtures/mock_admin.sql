@@ -0,0 +1,2 @@
+-- Promotes alice from the mock_users fixture to administrator.
+UPDATE users SET role = 'admin' WHERE id = 1;
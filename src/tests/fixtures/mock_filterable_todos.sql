@@ -0,0 +1,5 @@
+INSERT INTO todos (title, content, completed, completed_at, due_at, owner_id, position)
+VALUES ('buy milk', 'from the corner shop', FALSE, NULL, '2030-01-03T00:00:00Z', 1, 1024),
+    ('write report', 'quarterly numbers for the shop', TRUE, '2030-01-01T00:00:00Z', '2030-01-01T00:00:00Z', 1, 2048),
+    ('call mom', 'ask about the weekend', FALSE, NULL, NULL, 1, 3072),
+    ('buy flowers', 'for the anniversary', TRUE, '2030-01-02T00:00:00Z', '2030-01-02T00:00:00Z', 1, 4096);
@@ -0,0 +1 @@
+INSERT INTO todos (title, content, due_at, owner_id, position) VALUES ('mock-title', 'mock-content', '2030-01-01T09:00:00Z', 1, 1024);
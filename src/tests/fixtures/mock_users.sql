@@ -0,0 +1,4 @@
+-- Both users share the password "password123".
+INSERT INTO users (email, password_hash)
+VALUES ('alice@example.com', '$argon2id$v=19$m=19456,t=2,p=1$42UopNq6LF9K3operwjw6A$RxeHw6aAt2hbOF6zSsGjYWlHL6V2AizUlxx0+oBEhcI'),
+    ('bob@example.com', '$argon2id$v=19$m=19456,t=2,p=1$42UopNq6LF9K3operwjw6A$RxeHw6aAt2hbOF6zSsGjYWlHL6V2AizUlxx0+oBEhcI');
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        configs::state::AppState,
+        modules::{
+            admin::service::*,
+            auth::{
+                models::{RefreshTokenFields, Rotation},
+                service::AuthService,
+            },
+            users::models::Role,
+        },
+    };
+    use chrono::{Duration, Utc};
+    use sqlx::PgPool;
+    use uuid::Uuid;
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn disable_user_ok(pool: PgPool) {
+        let state = Arc::new(AppState::new(pool));
+        let auth_service = AuthService::new(state.clone());
+        auth_service
+            .create(&RefreshTokenFields {
+                user_id: 2,
+                family_id: Uuid::new_v4(),
+                token_hash: "bob".to_string(),
+                expires_at: Utc::now() + Duration::hours(1),
+            })
+            .await
+            .unwrap();
+
+        let service = AdminService::new(state);
+        let disabled = service.disable_user(2).await.unwrap();
+        assert!(disabled.is_disabled());
+        let disabled_again = service.disable_user(2).await.unwrap();
+        assert_eq!(disabled_again.disabled_at, disabled.disabled_at);
+        assert!(matches!(
+            auth_service.rotate("bob", "new", Utc::now() + Duration::hours(1)).await,
+            Ok(Rotation::Rejected)
+        ));
+
+        assert!(!service.enable_user(2).await.unwrap().is_disabled());
+        assert!(matches!(service.disable_user(99).await, Err(sqlx::Error::RowNotFound)));
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn grant_admin_ok(pool: PgPool) {
+        let service = AdminService::new(Arc::new(AppState::new(pool)));
+
+        assert!(service.grant_admin("bob@example.com").await.unwrap());
+        assert!(!service.grant_admin("carol@example.com").await.unwrap());
+        let users = service.list_users(10, 0).await.unwrap();
+        assert_eq!(users.iter().map(|user| user.role).collect::<Vec<_>>(), [Role::User, Role::Admin]);
+        assert_eq!(service.count_users().await.unwrap(), 2);
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        configs::state::AppState,
+        modules::api_keys::{
+            models::{ApiKeyFields, ApiKeyScope},
+            service::*,
+        },
+    };
+    use sqlx::PgPool;
+
+    fn fields(key_hash: &str) -> ApiKeyFields {
+        ApiKeyFields {
+            name: "ci".to_string(),
+            prefix: "tdk_abcdefgh".to_string(),
+            key_hash: key_hash.to_string(),
+            scope: ApiKeyScope::ReadOnly,
+        }
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn create_and_list_ok(pool: PgPool) {
+        let state = Arc::new(AppState::new(pool));
+        let service = ApiKeyService::new(state.clone(), 1);
+        let api_key = service.create(&fields("hash-1")).await.unwrap();
+        assert_eq!(api_key.user_id, 1);
+        assert_eq!(api_key.scope, ApiKeyScope::ReadOnly);
+        assert_eq!(api_key.last_used_at, None);
+
+        assert_eq!(service.list().await.unwrap().len(), 1);
+        assert_eq!(ApiKeyService::new(state, 2).list().await.unwrap().len(), 0);
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn authenticate_ok(pool: PgPool) {
+        let state = Arc::new(AppState::new(pool));
+        let service = ApiKeyService::new(state.clone(), 1);
+        service.create(&fields("hash-1")).await.unwrap();
+
+        let api_key = authenticate(&state.db_pool, "hash-1").await.unwrap();
+        assert_eq!(api_key.user_id, 1);
+        assert!(api_key.last_used_at.is_some());
+        assert!(matches!(
+            authenticate(&state.db_pool, "hash-2").await,
+            Err(sqlx::Error::RowNotFound)
+        ));
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn revoke_ok(pool: PgPool) {
+        let state = Arc::new(AppState::new(pool));
+        let service = ApiKeyService::new(state.clone(), 1);
+        let api_key = service.create(&fields("hash-1")).await.unwrap();
+
+        assert!(matches!(
+            ApiKeyService::new(state.clone(), 2).revoke(api_key.id).await,
+            Err(sqlx::Error::RowNotFound)
+        ));
+        let revoked = service.revoke(api_key.id).await.unwrap();
+        assert!(revoked.revoked_at.is_some());
+        assert_eq!(service.revoke(api_key.id).await.unwrap().revoked_at, revoked.revoked_at);
+        assert!(matches!(
+            authenticate(&state.db_pool, "hash-1").await,
+            Err(sqlx::Error::RowNotFound)
+        ));
+    }
+}
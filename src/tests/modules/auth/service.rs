@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        configs::state::AppState,
+        modules::auth::{
+            models::{RefreshTokenFields, Rotation},
+            service::*,
+        },
+    };
+    use chrono::{Duration, Utc};
+    use sqlx::PgPool;
+    use uuid::Uuid;
+
+    fn fields(token_hash: &str, expires_in: Duration) -> RefreshTokenFields {
+        RefreshTokenFields {
+            user_id: 1,
+            family_id: Uuid::new_v4(),
+            token_hash: token_hash.to_string(),
+            expires_at: Utc::now() + expires_in,
+        }
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn rotate_ok(pool: PgPool) {
+        let service = AuthService::new(Arc::new(AppState::new(pool)));
+        let created = service.create(&fields("first", Duration::hours(1))).await.unwrap();
+
+        match service.rotate("first", "second", Utc::now() + Duration::hours(1)).await {
+            Ok(Rotation::Rotated(rotated)) => {
+                assert_ne!(rotated.id, created.id);
+                assert_eq!(rotated.user_id, 1);
+                assert_eq!(rotated.family_id, created.family_id);
+                assert_eq!(rotated.revoked_at, None);
+            }
+            result => panic!("expected: rotated, got: {:?}", result),
+        }
+        assert!(matches!(
+            service.rotate("unknown", "third", Utc::now()).await,
+            Ok(Rotation::Rejected)
+        ));
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn rotate_err_expired(pool: PgPool) {
+        let service = AuthService::new(Arc::new(AppState::new(pool)));
+        service.create(&fields("expired", -Duration::minutes(1))).await.unwrap();
+
+        assert!(matches!(
+            service.rotate("expired", "second", Utc::now() + Duration::hours(1)).await,
+            Ok(Rotation::Rejected)
+        ));
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn rotate_err_disabled(pool: PgPool) {
+        let service = AuthService::new(Arc::new(AppState::new(pool.clone())));
+        service.create(&fields("first", Duration::hours(1))).await.unwrap();
+        sqlx::query("UPDATE users SET disabled_at = now() WHERE id = 1;")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            service.rotate("first", "second", Utc::now() + Duration::hours(1)).await,
+            Ok(Rotation::Disabled)
+        ));
+        sqlx::query("UPDATE users SET disabled_at = NULL WHERE id = 1;")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(matches!(
+            service.rotate("first", "second", Utc::now() + Duration::hours(1)).await,
+            Ok(Rotation::Rotated(_))
+        ));
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn revoke_family_ok(pool: PgPool) {
+        let service = AuthService::new(Arc::new(AppState::new(pool)));
+        service.create(&fields("first", Duration::hours(1))).await.unwrap();
+        service.rotate("first", "second", Utc::now() + Duration::hours(1)).await.unwrap();
+
+        assert_eq!(service.revoke_family("first").await.unwrap(), 1);
+        assert_eq!(service.revoke_family("second").await.unwrap(), 0);
+        assert_eq!(service.revoke_family("unknown").await.unwrap(), 0);
+    }
+}
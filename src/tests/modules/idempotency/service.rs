@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        configs::state::AppState,
+        modules::idempotency::{models::StoredResponse, service::*},
+    };
+    use chrono::{Duration, Utc};
+    use sqlx::PgPool;
+
+    fn abandoned_before() -> chrono::DateTime<Utc> {
+        Utc::now() - Duration::minutes(1)
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn claim_complete_find_ok(pool: PgPool) {
+        let state = Arc::new(AppState::new(pool));
+        let service = IdempotencyService::new(state.clone(), 1);
+        assert!(service.claim("retry-1", "fingerprint", abandoned_before()).await.unwrap());
+        assert!(!service.claim("retry-1", "other", abandoned_before()).await.unwrap());
+        // Keys belong to a single user.
+        assert!(IdempotencyService::new(state, 2).claim("retry-1", "other", abandoned_before()).await.unwrap());
+
+        let pending = service.find("retry-1").await.unwrap();
+        assert_eq!(pending.fingerprint, "fingerprint");
+        assert_eq!(pending.status_code, None);
+
+        let response = StoredResponse {
+            status_code: 201,
+            headers: vec!["content-type: application/json".to_string()],
+            body: b"{}".to_vec(),
+        };
+        service.complete("retry-1", &response).await.unwrap();
+        let stored = service.find("retry-1").await.unwrap();
+        assert_eq!(stored.status_code, Some(201));
+        assert_eq!(stored.headers, response.headers);
+        assert_eq!(stored.body, Some(response.body));
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn release_and_delete_expired_ok(pool: PgPool) {
+        let service = IdempotencyService::new(Arc::new(AppState::new(pool)), 1);
+        service.claim("retry-1", "fingerprint", abandoned_before()).await.unwrap();
+        service.release("retry-1").await.unwrap();
+        assert!(matches!(service.find("retry-1").await, Err(sqlx::Error::RowNotFound)));
+
+        service.claim("retry-2", "fingerprint", abandoned_before()).await.unwrap();
+        assert_eq!(service.delete_expired(Utc::now() - Duration::hours(1)).await.unwrap(), 0);
+        assert_eq!(service.delete_expired(Utc::now() + Duration::hours(1)).await.unwrap(), 1);
+        assert!(service.claim("retry-2", "other", abandoned_before()).await.unwrap());
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn claim_ok_takes_over_abandoned_key(pool: PgPool) {
+        let service = IdempotencyService::new(Arc::new(AppState::new(pool)), 1);
+        service.claim("retry-1", "fingerprint", abandoned_before()).await.unwrap();
+        let later = Utc::now() + Duration::minutes(1);
+        assert!(service.claim("retry-1", "other", later).await.unwrap());
+        assert_eq!(service.find("retry-1").await.unwrap().fingerprint, "other");
+
+        let response = StoredResponse {
+            status_code: 204,
+            headers: Vec::new(),
+            body: Vec::new(),
+        };
+        service.complete("retry-1", &response).await.unwrap();
+        let later = Utc::now() + Duration::minutes(1);
+        assert!(!service.claim("retry-1", "fingerprint", later).await.unwrap());
+    }
+}
@@ -0,0 +1,11 @@
+-- Alice owns "work" (two open todos, one completed) and "home" (empty); bob owns "errands".
+INSERT INTO lists (owner_id, name, color)
+VALUES (1, 'work', '#1e90ff'),
+    (1, 'home', NULL),
+    (2, 'errands', NULL);
+
+INSERT INTO todos (title, content, completed, owner_id, list_id, position)
+VALUES ('write report', 'quarterly', false, 1, 1, 1024),
+    ('review pr', 'backlog', false, 1, 1, 2048),
+    ('book flights', 'offsite', true, 1, 1, 3072),
+    ('buy milk', 'oat', false, 2, 3, 1024);
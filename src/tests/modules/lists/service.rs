@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+    use crate::modules::{
+        lists::{
+            models::{ListChanges, ListFields, ListFilter},
+            service::*,
+        },
+        todos::{models::TodoFilter, service::TodoService},
+    };
+    use sqlx::PgPool;
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_lists"))]
+    async fn find_ok_counts(pool: PgPool) {
+        let service = ListService::new(1);
+
+        let work = service.find(&pool, 1).await.unwrap();
+        assert_eq!(work.name, "work");
+        assert_eq!((work.open_count, work.completed_count), (2, 1));
+        let home = service.find(&pool, 2).await.unwrap();
+        assert_eq!((home.open_count, home.completed_count), (0, 0));
+        assert!(matches!(service.find(&pool, 3).await, Err(sqlx::Error::RowNotFound)));
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_lists"))]
+    async fn list_ok(pool: PgPool) {
+        let service = ListService::new(1);
+        service
+            .patch(
+                &pool,
+                2,
+                &ListChanges {
+                    archived: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let lists = service.list(&pool, &ListFilter::default(), 10, 0).await.unwrap();
+        assert_eq!(lists.iter().map(|list| list.id).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(lists[0].total, 2);
+        let filter = ListFilter {
+            archived: Some(false),
+        };
+        let lists = service.list(&pool, &filter, 10, 0).await.unwrap();
+        assert_eq!(lists.iter().map(|list| list.id).collect::<Vec<_>>(), [1]);
+        assert_eq!(lists[0].open_count, 2);
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_lists"))]
+    async fn lock_ok(pool: PgPool) {
+        let mut transaction = pool.begin().await.unwrap();
+
+        ListService::new(1).lock(&mut *transaction, 1).await.unwrap();
+        let other_owner = ListService::new(2).lock(&mut *transaction, 1).await;
+        assert!(matches!(other_owner, Err(sqlx::Error::RowNotFound)));
+        let locked = sqlx::query("SELECT id FROM lists WHERE id = 1 FOR UPDATE NOWAIT;")
+            .execute(&pool)
+            .await;
+        assert!(locked.is_err());
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn create_and_patch_ok(pool: PgPool) {
+        let service = ListService::new(1);
+
+        let list = service
+            .create(&pool, &ListFields {
+                name: "garden".to_string(),
+                color: Some("#00ff00".to_string()),
+                archived: false,
+            })
+            .await
+            .unwrap();
+        assert_eq!((list.open_count, list.completed_count), (0, 0));
+
+        let changes = ListChanges {
+            color: Some(None),
+            ..Default::default()
+        };
+        let patched = service.patch(&pool, list.id, &changes).await.unwrap();
+        assert_eq!(patched.name, "garden");
+        assert_eq!(patched.color, None);
+        assert!(patched.updated_at > patched.created_at);
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_lists"))]
+    async fn delete_ok_trashes_todos(pool: PgPool) {
+        let service = ListService::new(1);
+
+        assert_eq!(service.delete(&pool, 1, None).await.unwrap(), 1);
+        let todos = TodoService::new(1);
+        assert_eq!(todos.count(&pool, &TodoFilter::default()).await.unwrap(), 0);
+        let trash = todos.list_trash(&pool, 10, 0).await.unwrap();
+        assert_eq!(trash.len(), 3);
+        assert!(trash.iter().all(|todo| todo.list_id.is_none()));
+        assert_eq!(service.delete(&pool, 3, None).await.unwrap(), 0);
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_lists"))]
+    async fn delete_ok_keeps_todos_of_removed_list(pool: PgPool) {
+        // Rows removed outside `ListService::delete` detach their todos too.
+        sqlx::query("DELETE FROM lists WHERE id = 1;").execute(&pool).await.unwrap();
+
+        let todos = TodoService::new(1);
+        let filter = TodoFilter::default();
+        let remaining = todos.list(&pool, &filter, 10, 0).await.unwrap();
+        assert_eq!(remaining.len(), 3);
+        assert!(remaining.iter().all(|todo| todo.list_id.is_none()));
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_lists"))]
+    async fn delete_ok_reassign(pool: PgPool) {
+        let service = ListService::new(1);
+
+        assert_eq!(service.delete(&pool, 1, Some(2)).await.unwrap(), 1);
+        let home = service.find(&pool, 2).await.unwrap();
+        assert_eq!((home.open_count, home.completed_count), (2, 1));
+        let bob_todos = TodoService::new(2);
+        assert_eq!(bob_todos.count(&pool, &TodoFilter::default()).await.unwrap(), 1);
+    }
+}
@@ -1 +1,9 @@
-mod todos;
\ No newline at end of file
+mod admin;
+mod api_keys;
+mod auth;
+mod idempotency;
+mod lists;
+mod tags;
+mod todo_items;
+mod todos;
+mod users;
\ No newline at end of file
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use crate::modules::{tags::service::*, todos::service::TodoService};
+    use sqlx::PgPool;
+
+    #[sqlx::test(
+        fixtures(path = "../../fixtures", scripts("mock_users")),
+        fixtures(path = "../todos/fixtures", scripts("mock_todos"))
+    )]
+    async fn add_to_todo_ok(pool: PgPool) {
+        let service = TagService::new(1);
+
+        service.add_to_todo(&pool, 1, "work").await.unwrap();
+        service.add_to_todo(&pool, 1, "urgent").await.unwrap();
+        service.add_to_todo(&pool, 1, "work").await.unwrap();
+        service.add_to_todo(&pool, 2, "work").await.unwrap();
+
+        let todo = TodoService::new(1).find(&pool, 1).await.unwrap();
+        assert_eq!(todo.tags, ["urgent", "work"]);
+        let tags = service.list(&pool).await.unwrap();
+        assert_eq!(
+            tags.iter().map(|tag| (tag.name.as_str(), tag.usage_count)).collect::<Vec<_>>(),
+            [("urgent", 1), ("work", 2)]
+        );
+    }
+
+    #[sqlx::test(
+        fixtures(path = "../../fixtures", scripts("mock_users")),
+        fixtures(path = "../todos/fixtures", scripts("mock_todos"))
+    )]
+    async fn remove_from_todo_ok(pool: PgPool) {
+        let service = TagService::new(1);
+        service.add_to_todo(&pool, 1, "work").await.unwrap();
+
+        assert_eq!(service.remove_from_todo(&pool, 1, "work").await.unwrap(), 1);
+        assert_eq!(service.remove_from_todo(&pool, 1, "work").await.unwrap(), 0);
+        let tags = service.list(&pool).await.unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].usage_count, 0);
+    }
+
+    #[sqlx::test(
+        fixtures(path = "../../fixtures", scripts("mock_users")),
+        fixtures(path = "../todos/fixtures", scripts("mock_todos"))
+    )]
+    async fn add_to_todo_ok_foreign_todo(pool: PgPool) {
+        let service = TagService::new(2);
+
+        service.add_to_todo(&pool, 1, "mine").await.unwrap();
+        let tags = service.list(&pool).await.unwrap();
+        assert_eq!(tags[0].usage_count, 0);
+    }
+}
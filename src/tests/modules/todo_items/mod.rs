@@ -0,0 +1 @@
+mod service;
\ No newline at end of file
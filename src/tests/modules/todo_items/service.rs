@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use crate::modules::{
+        todo_items::{models::TodoItemChanges, service::*},
+        todos::{
+            models::Progress,
+            service::{purge_trash, TodoService},
+        },
+    };
+    use chrono::{Duration, Utc};
+    use sqlx::PgPool;
+
+    async fn titles(pool: &PgPool, service: &TodoItemService) -> Vec<(String, i32)> {
+        service
+            .list(pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|item| (item.title, item.position))
+            .collect()
+    }
+
+    #[sqlx::test(
+        fixtures(path = "../../fixtures", scripts("mock_users")),
+        fixtures(path = "../todos/fixtures", scripts("mock_todos"))
+    )]
+    async fn create_ok(pool: PgPool) {
+        let service = TodoItemService::new(1);
+
+        for title in ["eggs", "flour", "sugar"] {
+            service.create(&pool, title).await.unwrap();
+        }
+        TodoItemService::new(2).create(&pool, "other").await.unwrap();
+        assert_eq!(
+            titles(&pool, &service).await,
+            [("eggs".to_string(), 0), ("flour".to_string(), 1), ("sugar".to_string(), 2)]
+        );
+
+        let changes = TodoItemChanges {
+            done: Some(true),
+            ..Default::default()
+        };
+        let item = service.patch(&pool, 2, &changes).await.unwrap();
+        assert!(item.done);
+        assert_eq!(item.title, "flour");
+        let todo = TodoService::new(1).find(&pool, 1).await.unwrap();
+        assert_eq!(todo.progress, Progress { done: 1, total: 3 });
+    }
+
+    #[sqlx::test(
+        fixtures(path = "../../fixtures", scripts("mock_users")),
+        fixtures(path = "../todos/fixtures", scripts("mock_todos"))
+    )]
+    async fn delete_ok(pool: PgPool) {
+        let service = TodoItemService::new(1);
+        for title in ["eggs", "flour", "sugar"] {
+            service.create(&pool, title).await.unwrap();
+        }
+
+        assert_eq!(service.delete(&pool, 1).await.unwrap(), 1);
+        assert_eq!(service.delete(&pool, 1).await.unwrap(), 0);
+        assert_eq!(
+            titles(&pool, &service).await,
+            [("flour".to_string(), 0), ("sugar".to_string(), 1)]
+        );
+        service.create(&pool, "milk").await.unwrap();
+        assert_eq!(titles(&pool, &service).await.last().unwrap().1, 2);
+    }
+
+    #[sqlx::test(
+        fixtures(path = "../../fixtures", scripts("mock_users")),
+        fixtures(path = "../todos/fixtures", scripts("mock_todos"))
+    )]
+    async fn reorder_ok(pool: PgPool) {
+        let service = TodoItemService::new(1);
+        for title in ["eggs", "flour", "sugar"] {
+            service.create(&pool, title).await.unwrap();
+        }
+
+        let items = service.reorder(&pool, &[3, 1, 2]).await.unwrap();
+        assert_eq!(items.iter().map(|item| item.id).collect::<Vec<_>>(), [3, 1, 2]);
+        assert_eq!(items.iter().map(|item| item.position).collect::<Vec<_>>(), [0, 1, 2]);
+    }
+
+    #[sqlx::test(
+        fixtures(path = "../../fixtures", scripts("mock_users")),
+        fixtures(path = "../todos/fixtures", scripts("mock_todos"))
+    )]
+    async fn delete_ok_with_purged_parent(pool: PgPool) {
+        let service = TodoItemService::new(1);
+        service.create(&pool, "eggs").await.unwrap();
+
+        TodoService::new(1).delete(&pool, 1).await.unwrap();
+        assert_eq!(service.list(&pool).await.unwrap().len(), 1);
+
+        purge_trash(&pool, Utc::now() + Duration::seconds(1)).await.unwrap();
+        assert!(service.list(&pool).await.unwrap().is_empty());
+    }
+}
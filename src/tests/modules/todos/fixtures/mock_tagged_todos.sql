@@ -0,0 +1,9 @@
+-- Tags the mock_filterable_todos: "buy milk" is errand+urgent, "buy flowers"
+-- is errand, "write report" is urgent+work.
+INSERT INTO tags (owner_id, name)
+VALUES (1, 'errand'),
+    (1, 'urgent'),
+    (1, 'work');
+
+INSERT INTO todo_tags (todo_id, tag_id)
+VALUES (1, 1), (1, 2), (4, 1), (2, 2), (2, 3);
@@ -1 +1 @@
-INSERT INTO todos (title, content) VALUES ('mock-title','mock-content');
\ No newline at end of file
+INSERT INTO todos (title, content, owner_id, position) VALUES ('mock-title','mock-content', 1, 1024);
\ No newline at end of file
@@ -1,4 +1,4 @@
-INSERT INTO todos (title, content)
-VALUES ('mock-title-1', 'mock-content-1'),
-    ('mock-title-2', 'mock-content-2'),
-    ('mock-title-3', 'mock-content-3');
\ No newline at end of file
+INSERT INTO todos (title, content, owner_id, position)
+VALUES ('mock-title-1', 'mock-content-1', 1, 1024),
+    ('mock-title-2', 'mock-content-2', 1, 2048),
+    ('mock-title-3', 'mock-content-3', 1, 3072);
\ No newline at end of file
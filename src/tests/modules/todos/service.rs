@@ -1,15 +1,24 @@
 #[cfg(test)]
 mod tests {
-    use std::{any::Any, sync::Arc};
+    use std::any::Any;
 
-    use crate::{configs::state::AppState, modules::todos::service::*};
+    use crate::{
+        modules::todos::{
+            models::{
+                BulkOperation, BulkOutcome, Placement, SortDirection, SortField, TagMatch,
+                TodoChanges, TodoFields, TodoFilter, TodoSort,
+            },
+            service::*,
+        },
+    };
+    use chrono::{TimeZone, Utc};
     use sqlx::PgPool;
     use uuid::Uuid;
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
     async fn empty_list_ok(pool: PgPool) {
-        let service = TodoService::new(Arc::new(AppState { db_pool: pool }));
-        match service.list(10, 0).await {
+        let service = TodoService::new(1);
+        match service.list(&pool, &TodoFilter::default(), 10, 0).await {
             Err(error) => panic!("{}", error),
             Ok(todos) => {
                 assert_eq!(todos.len(), 0);
@@ -17,26 +26,36 @@ mod tests {
         }
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
     async fn create_ok(pool: PgPool) {
-        let service = TodoService::new(Arc::new(AppState { db_pool: pool }));
+        let service = TodoService::new(1);
         let random_title = Uuid::new_v4();
         let random_content = Uuid::new_v4();
-        match service.create(format!("title-{}", random_title).as_str(), format!("content-{}", random_content).as_str()).await {
-            
+        let fields = TodoFields {
+            title: format!("title-{}", random_title),
+            content: format!("content-{}", random_content),
+            completed: false,
+            due_at: None,
+            list_id: None,
+        };
+        match service.create(&pool, &fields).await {
             Err(error) => panic!("{}", error),
             Ok(todo) => {
                 assert_eq!(todo.id, 1);
                 assert_eq!(todo.title, format!("title-{}", random_title));
                 assert_eq!(todo.content, format!("content-{}", random_content));
+                assert!(!todo.completed);
+                assert_eq!(todo.completed_at, None);
+                assert_eq!(todo.due_at, None);
+                assert_eq!(todo.created_at, todo.updated_at);
             }
         }
     }
 
-    #[sqlx::test(fixtures("mock_todo"))]
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todo"))]
     async fn find_ok(pool: PgPool) {
-        let service = TodoService::new(Arc::new(AppState { db_pool: pool }));
-        match service.find(1).await {
+        let service = TodoService::new(1);
+        match service.find(&pool, 1).await {
             Err(error) => panic!("{}", error),
             Ok(todo) => {
                 assert_eq!(todo.id, 1);
@@ -46,11 +65,11 @@ mod tests {
         }
     }
 
-    #[sqlx::test(fixtures("mock_todos"))]
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todos"))]
     async fn list_ok(pool: PgPool) {
-        let service = TodoService::new(Arc::new(AppState { db_pool: pool }));
+        let service = TodoService::new(1);
 
-        match service.list(10, 0).await {
+        match service.list(&pool, &TodoFilter::default(), 10, 0).await {
             Err(error) => panic!("{}", error),
             Ok(todos) => {
                 assert_eq!(todos.len(), 3);
@@ -71,11 +90,11 @@ mod tests {
         }
     }
 
-    #[sqlx::test(fixtures("mock_todos"))]
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todos"))]
     async fn update_ok(pool: PgPool) {
-        let service = TodoService::new(Arc::new(AppState { db_pool: pool }));
+        let service = TodoService::new(1);
 
-        match service.find(3).await {
+        match service.find(&pool, 3).await {
             Err(error) => panic!("{}", error),
             Ok(todo) => {
                 assert_eq!(todo.id, 3);
@@ -84,16 +103,24 @@ mod tests {
             }
         }
 
-        match service.update(3, "foobar", "fozbaz").await {
+        let fields = TodoFields {
+            title: "foobar".to_string(),
+            content: "fozbaz".to_string(),
+            completed: false,
+            due_at: None,
+            list_id: None,
+        };
+        match service.update(&pool, 3, &fields).await {
             Err(error) => panic!("{}", error),
             Ok(todo) => {
                 assert_eq!(todo.id, 3);
                 assert_eq!(todo.title, "foobar");
                 assert_eq!(todo.content, "fozbaz");
+                assert!(todo.updated_at > todo.created_at);
             }
         }
 
-        match service.find(3).await {
+        match service.find(&pool, 3).await {
             Err(error) => panic!("{}", error),
             Ok(todo) => {
                 assert_eq!(todo.id, 3);
@@ -103,11 +130,11 @@ mod tests {
         }
     }
 
-    #[sqlx::test(fixtures("mock_todos"))]
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todos"))]
     async fn delete_ok_find_err_not_found(pool: PgPool) {
-        let service = TodoService::new(Arc::new(AppState { db_pool: pool }));
+        let service = TodoService::new(1);
 
-        match service.list(10, 0).await {
+        match service.list(&pool, &TodoFilter::default(), 10, 0).await {
             Err(error) => panic!("{}", error),
             Ok(todos) => {
                 assert_eq!(todos.len(), 3);
@@ -127,14 +154,14 @@ mod tests {
             }
         }
 
-        match service.delete(1).await {
+        match service.delete(&pool, 1).await {
             Err(error) => panic!("{}", error),
             Ok(pq_query_result) => {
                 assert_eq!(pq_query_result.rows_affected(), 1);
             }
         }
 
-        match service.find(1).await {
+        match service.find(&pool, 1).await {
             Err(error) => {
                 assert_eq!(error.type_id(),
                 sqlx::Error::RowNotFound.type_id());
@@ -144,7 +171,7 @@ mod tests {
             }
         }
 
-        match service.list(10, 0).await {
+        match service.list(&pool, &TodoFilter::default(), 10, 0).await {
             Err(error) => panic!("{}", error),
             Ok(todos) => {
                 assert_eq!(todos.len(), 2);
@@ -160,4 +187,455 @@ mod tests {
             }
         }
     }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todo"))]
+    async fn update_completed_ok(pool: PgPool) {
+        let service = TodoService::new(1);
+        let due_at = Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap();
+        let mut fields = TodoFields {
+            title: "mock-title".to_string(),
+            content: "mock-content".to_string(),
+            completed: true,
+            due_at: Some(due_at),
+            list_id: None,
+        };
+
+        let completed_at = match service.update(&pool, 1, &fields).await {
+            Err(error) => panic!("{}", error),
+            Ok(todo) => {
+                assert!(todo.completed);
+                assert_eq!(todo.due_at, Some(due_at));
+                todo.completed_at.expect("expected: completed_at to be set")
+            }
+        };
+
+        match service.update(&pool, 1, &fields).await {
+            Err(error) => panic!("{}", error),
+            Ok(todo) => assert_eq!(todo.completed_at, Some(completed_at)),
+        }
+
+        fields.completed = false;
+        match service.update(&pool, 1, &fields).await {
+            Err(error) => panic!("{}", error),
+            Ok(todo) => {
+                assert!(!todo.completed);
+                assert_eq!(todo.completed_at, None);
+            }
+        }
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todos"))]
+    async fn patch_ok(pool: PgPool) {
+        let service = TodoService::new(1);
+        let due_at = Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap();
+
+        let changes = TodoChanges {
+            title: Some("foobar".to_string()),
+            due_at: Some(Some(due_at)),
+            ..Default::default()
+        };
+        match service.patch(&pool, 2, &changes).await {
+            Err(error) => panic!("{}", error),
+            Ok(todo) => {
+                assert_eq!(todo.id, 2);
+                assert_eq!(todo.title, "foobar");
+                assert_eq!(todo.content, "mock-content-2");
+                assert_eq!(todo.due_at, Some(due_at));
+                assert!(todo.updated_at > todo.created_at);
+            }
+        }
+
+        let changes = TodoChanges {
+            completed: Some(true),
+            due_at: Some(None),
+            ..Default::default()
+        };
+        match service.patch(&pool, 2, &changes).await {
+            Err(error) => panic!("{}", error),
+            Ok(todo) => {
+                assert_eq!(todo.title, "foobar");
+                assert!(todo.completed);
+                assert!(todo.completed_at.is_some());
+                assert_eq!(todo.due_at, None);
+            }
+        }
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn patch_err_not_found(pool: PgPool) {
+        let service = TodoService::new(1);
+        let changes = TodoChanges {
+            completed: Some(true),
+            ..Default::default()
+        };
+        match service.patch(&pool, 9999, &changes).await {
+            Err(sqlx::Error::RowNotFound) => {}
+            result => panic!("expected: not found, got: {:?}", result),
+        }
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users", "mock_filterable_todos")))]
+    async fn list_with_filter_ok(pool: PgPool) {
+        let service = TodoService::new(1);
+
+        let filter = TodoFilter {
+            search: Some("shop".to_string()),
+            ..Default::default()
+        };
+        match service.list(&pool, &filter, 10, 0).await {
+            Err(error) => panic!("{}", error),
+            Ok(todos) => {
+                assert_eq!(todos.len(), 2);
+                assert_eq!(todos[0].total, 2);
+                assert_eq!(todos[0].title, "buy milk");
+                assert_eq!(todos[1].title, "write report");
+            }
+        }
+
+        let filter = TodoFilter {
+            completed: Some(false),
+            due_after: Some(Utc.with_ymd_and_hms(2030, 1, 2, 0, 0, 0).unwrap()),
+            ..Default::default()
+        };
+        match service.list(&pool, &filter, 10, 0).await {
+            Err(error) => panic!("{}", error),
+            Ok(todos) => {
+                assert_eq!(todos.len(), 1);
+                assert_eq!(todos[0].title, "buy milk");
+            }
+        }
+
+        let filter = TodoFilter {
+            due_before: Some(Utc.with_ymd_and_hms(2030, 1, 3, 0, 0, 0).unwrap()),
+            ..Default::default()
+        };
+        match service.list(&pool, &filter, 10, 0).await {
+            Err(error) => panic!("{}", error),
+            Ok(todos) => {
+                assert_eq!(todos.len(), 2);
+                assert_eq!(todos[0].total, 2);
+            }
+        }
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users", "mock_filterable_todos")))]
+    async fn list_with_sort_ok(pool: PgPool) {
+        let service = TodoService::new(1);
+
+        let filter = TodoFilter {
+            sort: TodoSort {
+                field: SortField::DueAt,
+                direction: SortDirection::Desc,
+            },
+            ..Default::default()
+        };
+        match service.list(&pool, &filter, 10, 0).await {
+            Err(error) => panic!("{}", error),
+            Ok(todos) => {
+                let titles: Vec<&str> = todos.iter().map(|todo| todo.title.as_str()).collect();
+                assert_eq!(titles, vec!["buy milk", "buy flowers", "write report", "call mom"]);
+            }
+        }
+
+        let filter = TodoFilter {
+            sort: TodoSort::parse("title").unwrap(),
+            ..Default::default()
+        };
+        match service.list(&pool, &filter, 10, 0).await {
+            Err(error) => panic!("{}", error),
+            Ok(todos) => {
+                let titles: Vec<&str> = todos.iter().map(|todo| todo.title.as_str()).collect();
+                assert_eq!(titles, vec!["buy flowers", "buy milk", "call mom", "write report"]);
+            }
+        }
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users", "mock_filterable_todos")))]
+    async fn list_page_after_ok(pool: PgPool) {
+        let service = TodoService::new(1);
+
+        for sort in ["due_at:asc", "due_at:desc", "title:desc", "completed:asc", "position:desc"] {
+            let filter = TodoFilter {
+                sort: TodoSort::parse(sort).unwrap(),
+                ..Default::default()
+            };
+            let expected: Vec<i32> = service
+                .list(&pool, &filter, 10, 0)
+                .await
+                .unwrap()
+                .iter()
+                .map(|todo| todo.id)
+                .collect();
+
+            let mut walked: Vec<i32> = vec![];
+            let mut after = None;
+            loop {
+                let page = service.list_page(&pool, &filter, 1, 0, after.as_ref()).await.unwrap();
+                match page.first() {
+                    None => break,
+                    Some(todo) => {
+                        walked.push(todo.id);
+                        after = Some(filter.sort.position_of(todo));
+                    }
+                }
+            }
+            assert_eq!(walked, expected, "sort: {}", sort);
+        }
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users", "mock_filterable_todos")))]
+    async fn count_ok(pool: PgPool) {
+        let service = TodoService::new(1);
+
+        assert_eq!(service.count(&pool, &TodoFilter::default()).await.unwrap(), 4);
+        let filter = TodoFilter {
+            completed: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(service.count(&pool, &filter).await.unwrap(), 2);
+    }
+
+    #[sqlx::test(
+        fixtures(path = "../../fixtures", scripts("mock_users", "mock_filterable_todos")),
+        fixtures("mock_tagged_todos")
+    )]
+    async fn list_with_tag_filter_ok(pool: PgPool) {
+        let service = TodoService::new(1);
+        let titles = |todos: Vec<crate::modules::todos::models::Todo>| {
+            todos.into_iter().map(|todo| todo.title).collect::<Vec<_>>()
+        };
+
+        let filter = TodoFilter {
+            tags: vec!["errand".to_string(), "work".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            titles(service.list(&pool, &filter, 10, 0).await.unwrap()),
+            ["buy milk", "write report", "buy flowers"]
+        );
+        let filter = TodoFilter {
+            tags: vec!["errand".to_string(), "urgent".to_string()],
+            tag_match: TagMatch::All,
+            ..Default::default()
+        };
+        let todos = service.list(&pool, &filter, 10, 0).await.unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].tags, ["errand", "urgent"]);
+        assert_eq!(service.count(&pool, &filter).await.unwrap(), 1);
+    }
+
+    async fn ordered_ids(pool: &PgPool, service: &TodoService) -> Vec<i32> {
+        service
+            .list(pool, &TodoFilter::default(), 10, 0)
+            .await
+            .unwrap()
+            .iter()
+            .map(|todo| todo.id)
+            .collect()
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todos"))]
+    async fn move_to_ok(pool: PgPool) {
+        let service = TodoService::new(1);
+
+        let moved = service.move_to(&pool, 3, 1, Placement::Before).await.unwrap();
+        assert_eq!(moved.position, 0);
+        assert_eq!(ordered_ids(&pool, &service).await, [3, 1, 2]);
+
+        let moved = service.move_to(&pool, 3, 1, Placement::After).await.unwrap();
+        assert_eq!(moved.position, 1536);
+        assert_eq!(ordered_ids(&pool, &service).await, [1, 3, 2]);
+
+        let moved = service.move_to(&pool, 1, 2, Placement::After).await.unwrap();
+        assert_eq!(moved.position, 3072);
+        assert_eq!(ordered_ids(&pool, &service).await, [3, 2, 1]);
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todos"))]
+    async fn move_to_ok_renumbers(pool: PgPool) {
+        let service = TodoService::new(1);
+
+        // Every move halves the gap after todo 1, which runs out after ten moves.
+        for _ in 0..6 {
+            service.move_to(&pool, 3, 1, Placement::After).await.unwrap();
+            assert_eq!(ordered_ids(&pool, &service).await, [1, 3, 2]);
+            service.move_to(&pool, 2, 1, Placement::After).await.unwrap();
+            assert_eq!(ordered_ids(&pool, &service).await, [1, 2, 3]);
+        }
+        let positions: Vec<i64> = service
+            .list(&pool, &TodoFilter::default(), 10, 0)
+            .await
+            .unwrap()
+            .iter()
+            .map(|todo| todo.position)
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todos"))]
+    async fn create_ok_appends(pool: PgPool) {
+        let service = TodoService::new(1);
+        service.move_to(&pool, 1, 3, Placement::After).await.unwrap();
+
+        let fields = TodoFields {
+            title: "last".to_string(),
+            content: String::new(),
+            completed: false,
+            due_at: None,
+            list_id: None,
+        };
+        let todo = service.create(&pool, &fields).await.unwrap();
+        assert_eq!(ordered_ids(&pool, &service).await, [2, 3, 1, todo.id]);
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todos"))]
+    async fn delete_ok_list_trash_restore_ok(pool: PgPool) {
+        let service = TodoService::new(1);
+        assert!(service.list_trash(&pool, 10, 0).await.unwrap().is_empty());
+
+        service.delete(&pool, 2).await.unwrap();
+        service.delete(&pool, 1).await.unwrap();
+        assert_eq!(service.delete(&pool, 1).await.unwrap().rows_affected(), 0);
+        assert_eq!(service.count(&pool, &TodoFilter::default()).await.unwrap(), 1);
+
+        let trash = service.list_trash(&pool, 10, 0).await.unwrap();
+        assert_eq!(trash.iter().map(|todo| todo.id).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(trash[0].total, 2);
+        assert!(trash.iter().all(|todo| todo.deleted_at.is_some()));
+
+        let todo = service.restore(&pool, 1).await.unwrap();
+        assert_eq!(todo.id, 1);
+        assert_eq!(todo.deleted_at, None);
+        assert_eq!(ordered_ids(&pool, &service).await, [1, 3]);
+        assert_eq!(service.list_trash(&pool, 10, 0).await.unwrap().len(), 1);
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todos"))]
+    async fn restore_err_not_trashed(pool: PgPool) {
+        let service = TodoService::new(1);
+        service.delete(&pool, 1).await.unwrap();
+
+        for (owner_id, id) in [(1, 2), (2, 1)] {
+            match TodoService::new(owner_id).restore(&pool, id).await {
+                Err(sqlx::Error::RowNotFound) => {}
+                result => panic!("expected RowNotFound, got {:?}", result.map(|todo| todo.id)),
+            }
+        }
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todos"))]
+    async fn purge_trash_ok_only_before_cutoff(pool: PgPool) {
+        let service = TodoService::new(1);
+        service.delete(&pool, 1).await.unwrap();
+        sqlx::query("UPDATE todos SET deleted_at = now() - interval '2 days' WHERE id = 1;")
+            .execute(&pool)
+            .await
+            .unwrap();
+        service.delete(&pool, 2).await.unwrap();
+
+        let purged = purge_trash(&pool, Utc::now() - chrono::Duration::days(1)).await.unwrap();
+        assert_eq!(purged, 1);
+        let trash = service.list_trash(&pool, 10, 0).await.unwrap();
+        assert_eq!(trash.iter().map(|todo| todo.id).collect::<Vec<_>>(), [2]);
+        assert_eq!(ordered_ids(&pool, &service).await, [3]);
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todos"))]
+    async fn update_ok_bumps_version(pool: PgPool) {
+        let service = TodoService::new(1);
+        assert_eq!(service.find(&pool, 1).await.unwrap().version, 1);
+
+        let fields = TodoFields {
+            title: "renamed".to_string(),
+            content: String::new(),
+            completed: false,
+            due_at: None,
+            list_id: None,
+        };
+        assert_eq!(service.update(&pool, 1, &fields).await.unwrap().version, 2);
+        let changes = TodoChanges {
+            completed: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(service.patch(&pool, 1, &changes).await.unwrap().version, 3);
+        assert_eq!(service.patch(&pool, 1, &TodoChanges::default()).await.unwrap().version, 3);
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todos"))]
+    async fn find_for_update_ok_locks_until_commit(pool: PgPool) {
+        let service = TodoService::new(1);
+        let mut transaction = pool.begin().await.unwrap();
+        let todo = service.find_for_update(&mut *transaction, 1).await.unwrap();
+        assert_eq!(todo.version, 1);
+
+        let mut other = pool.begin().await.unwrap();
+        sqlx::query("SET LOCAL lock_timeout = '100ms';")
+            .execute(&mut *other)
+            .await
+            .unwrap();
+        assert!(service.find_for_update(&mut *other, 1).await.is_err());
+        other.rollback().await.unwrap();
+
+        service.delete(&mut *transaction, 1).await.unwrap();
+        transaction.commit().await.unwrap();
+        assert!(matches!(service.find(&pool, 1).await, Err(sqlx::Error::RowNotFound)));
+    }
+
+    fn bulk_operations() -> Vec<BulkOperation> {
+        vec![
+            BulkOperation::Create(TodoFields {
+                title: "imported".to_string(),
+                content: String::new(),
+                completed: false,
+                due_at: None,
+                list_id: None,
+            }),
+            BulkOperation::Update {
+                id: 2,
+                changes: TodoChanges {
+                    completed: Some(true),
+                    ..Default::default()
+                },
+            },
+            BulkOperation::Delete { id: 99 },
+            BulkOperation::Delete { id: 3 },
+        ]
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todos"))]
+    async fn bulk_ok_rolls_back_on_error(pool: PgPool) {
+        let service = TodoService::new(1);
+
+        let outcomes = service.bulk(&pool, &bulk_operations(), false).await.unwrap();
+        assert_eq!(outcomes.len(), 3);
+        assert!(matches!(outcomes[0], Ok(BulkOutcome::Created(_))));
+        assert!(matches!(outcomes[1], Ok(BulkOutcome::Updated(ref todo)) if todo.completed));
+        assert!(matches!(outcomes[2], Err(sqlx::Error::RowNotFound)));
+        assert_eq!(ordered_ids(&pool, &service).await, [1, 2, 3]);
+        assert!(!service.find(&pool, 2).await.unwrap().completed);
+
+        let mut operations = bulk_operations();
+        operations.remove(2);
+        let outcomes = service.bulk(&pool, &operations, false).await.unwrap();
+        assert!(outcomes.iter().all(Result::is_ok));
+        assert!(matches!(outcomes[2], Ok(BulkOutcome::Deleted)));
+        let Ok(BulkOutcome::Created(created)) = &outcomes[0] else {
+            panic!("expected the todo to be created");
+        };
+        assert_eq!(ordered_ids(&pool, &service).await, [1, 2, created.id]);
+        assert!(service.find(&pool, 2).await.unwrap().completed);
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")), fixtures("mock_todos"))]
+    async fn bulk_ok_partial(pool: PgPool) {
+        let service = TodoService::new(1);
+
+        let outcomes = service.bulk(&pool, &bulk_operations(), true).await.unwrap();
+        assert_eq!(outcomes.len(), 4);
+        assert!(matches!(outcomes[2], Err(sqlx::Error::RowNotFound)));
+        assert!(matches!(outcomes[3], Ok(BulkOutcome::Deleted)));
+        assert_eq!(service.count(&pool, &TodoFilter::default()).await.unwrap(), 3);
+        assert!(service.find(&pool, 2).await.unwrap().completed);
+        assert!(matches!(service.find(&pool, 3).await, Err(sqlx::Error::RowNotFound)));
+    }
 }
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        configs::state::AppState,
+        modules::users::{models::UserFields, service::*},
+        utils::error::AppError,
+    };
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn create_ok(pool: PgPool) {
+        let service = UserService::new(Arc::new(AppState::new(pool)));
+        let fields = UserFields {
+            email: "carol@example.com".to_string(),
+            password_hash: "hash".to_string(),
+        };
+        match service.create(&fields).await {
+            Err(error) => panic!("{}", error),
+            Ok(user) => {
+                assert_eq!(user.id, 1);
+                assert_eq!(user.email, "carol@example.com");
+                assert_eq!(user.password_hash, "hash");
+                assert_eq!(user.created_at, user.updated_at);
+            }
+        }
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn create_err_duplicated_email(pool: PgPool) {
+        let service = UserService::new(Arc::new(AppState::new(pool)));
+        let fields = UserFields {
+            email: "alice@example.com".to_string(),
+            password_hash: "hash".to_string(),
+        };
+        match service.create(&fields).await {
+            Ok(user) => panic!("expected: conflict, got: {:?}", user),
+            Err(error) => assert!(matches!(AppError::from(error), AppError::Conflict)),
+        }
+    }
+
+    #[sqlx::test(fixtures(path = "../../fixtures", scripts("mock_users")))]
+    async fn find_by_email_ok(pool: PgPool) {
+        let service = UserService::new(Arc::new(AppState::new(pool)));
+        match service.find_by_email("bob@example.com").await {
+            Err(error) => panic!("{}", error),
+            Ok(user) => {
+                assert_eq!(user.id, 2);
+                assert_eq!(service.find(2).await.unwrap().email, "bob@example.com");
+            }
+        }
+        assert!(matches!(
+            service.find_by_email("nobody@example.com").await,
+            Err(sqlx::Error::RowNotFound)
+        ));
+    }
+}
@@ -2,23 +2,59 @@
 mod tests {
     use std::sync::Arc;
 
-    use crate::router::*;
+    use crate::{
+        configs::settings::Settings,
+        router::*,
+        utils::{
+            idempotency::{IDEMPOTENCY_KEY, IDEMPOTENT_REPLAYED},
+            jwt::JwtKeys,
+        },
+    };
     use axum::{
         body::Body,
         http::{self, Request, StatusCode},
     };
     use http_body_util::BodyExt;
+    use serde_json::{json, Value};
     use sqlx::PgPool;
     use tower::ServiceExt;
 
-    #[sqlx::test(fixtures("drop_todos_table"))]
+    // Ids of the users in the mock_users fixture.
+    const ALICE: i32 = 1;
+    const BOB: i32 = 2;
+
+    // Signed with the default settings used by `AppState::new`.
+    fn bearer(user_id: i32) -> String {
+        let keys = JwtKeys::new(&Settings::default().auth).unwrap();
+        format!("Bearer {}", keys.issue(user_id).unwrap())
+    }
+
+    fn without_timestamps(body: &str) -> Value {
+        fn strip(value: &mut Value) {
+            match value {
+                Value::Object(map) => {
+                    map.remove("created_at");
+                    map.remove("updated_at");
+                    map.values_mut().for_each(strip);
+                }
+                Value::Array(items) => items.iter_mut().for_each(strip),
+                _ => {}
+            }
+        }
+        let mut value: Value = serde_json::from_str(body).unwrap();
+        strip(&mut value);
+        value
+    }
+
+    #[sqlx::test(fixtures("mock_users", "drop_todos_table"))]
     async fn db_error(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let mut response = todo_router.clone()
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/")
                     .method("GET")
                     .body(Body::empty())
@@ -38,6 +74,27 @@ mod tests {
         response = todo_router.clone()
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/1")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        body = response.into_body().collect().await.unwrap().to_bytes();
+        string_body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(
+            string_body,
+            "{\"code\":500,\"message\":\"internal server error\"}"
+        );
+
+        response = todo_router.clone()
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/")
                     .method("POST")
                     .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
@@ -59,6 +116,7 @@ mod tests {
         response = todo_router.clone()
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/1")
                     .method("PUT")
                     .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
@@ -79,6 +137,7 @@ mod tests {
         response = todo_router.clone()
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/1")
                     .method("DELETE")
                     .body(Body::empty())
@@ -94,14 +153,15 @@ mod tests {
         );
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures("mock_users"))]
     async fn list_ok(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let response = todo_router
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/")
                     .method("GET")
                     .body(Body::empty())
@@ -119,14 +179,15 @@ mod tests {
         );
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures("mock_users"))]
     async fn list_with_limit_offset_ok(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let response = todo_router
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/?limit=5&&offset=20")
                     .method("GET")
                     .body(Body::empty())
@@ -144,14 +205,15 @@ mod tests {
         );
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures("mock_users"))]
     async fn list_with_over_limit_should_reduce_to_10(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let response = todo_router
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/?limit=9999&&offset=0")
                     .method("GET")
                     .body(Body::empty())
@@ -165,18 +227,19 @@ mod tests {
         let string_body = std::str::from_utf8(&body).unwrap();
         assert_eq!(
             string_body,
-            "{\"limit\":10,\"offset\":0,\"total\":0,\"items\":[]}"
+            "{\"limit\":10,\"requested_limit\":9999,\"offset\":0,\"total\":0,\"items\":[]}"
         );
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures("mock_users"))]
     async fn create_ok(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let response = todo_router
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/")
                     .method("POST")
                     .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
@@ -192,19 +255,20 @@ mod tests {
         let body = response.into_body().collect().await.unwrap().to_bytes();
         let string_body = std::str::from_utf8(&body).unwrap();
         assert_eq!(
-            string_body,
-            "{\"id\":1,\"title\":\"test-title\",\"content\":\"test-content\"}"
+            without_timestamps(string_body),
+            json!({"id":1,"title":"test-title","content":"test-content","completed":false,"completed_at":null,"due_at":null,"list_id":null,"position":1024,"tags":[],"progress":{"done":0,"total":0}})
         );
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures("mock_users"))]
     async fn find_not_found(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let response = todo_router
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/9999")
                     .method("GET")
                     .body(Body::empty())
@@ -219,14 +283,15 @@ mod tests {
         assert_eq!(string_body, "{\"code\":404,\"message\":\"not found\"}");
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures("mock_users"))]
     async fn find_err_non_numeric_id(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let response = todo_router
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/foobar")
                     .method("GET")
                     .body(Body::empty())
@@ -241,15 +306,16 @@ mod tests {
         assert_eq!(string_body, "{\"code\":400,\"message\":\"type of the following path is invalid\",\"path\":\"id\",\"comment\":\"expected type: interger\"}");
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures("mock_users"))]
     async fn create_and_find_ok(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let mut response = todo_router
             .clone()
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/")
                     .method("POST")
                     .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
@@ -266,14 +332,15 @@ mod tests {
         let mut body = response.into_body().collect().await.unwrap().to_bytes();
         let mut string_body = std::str::from_utf8(&body).unwrap();
         assert_eq!(
-            string_body,
-            "{\"id\":1,\"title\":\"test-title\",\"content\":\"test-content\"}"
+            without_timestamps(string_body),
+            json!({"id":1,"title":"test-title","content":"test-content","completed":false,"completed_at":null,"due_at":null,"list_id":null,"position":1024,"tags":[],"progress":{"done":0,"total":0}})
         );
 
         response = todo_router
             .clone()
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/1")
                     .method("GET")
                     .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
@@ -289,20 +356,21 @@ mod tests {
         body = response.into_body().collect().await.unwrap().to_bytes();
         string_body = std::str::from_utf8(&body).unwrap();
         assert_eq!(
-            string_body,
-            "{\"id\":1,\"title\":\"test-title\",\"content\":\"test-content\"}"
+            without_timestamps(string_body),
+            json!({"id":1,"title":"test-title","content":"test-content","completed":false,"completed_at":null,"due_at":null,"list_id":null,"position":1024,"tags":[],"progress":{"done":0,"total":0}})
         );
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures("mock_users"))]
     async fn create_and_list_ok(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let mut response = todo_router
             .clone()
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/")
                     .method("POST")
                     .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
@@ -319,14 +387,15 @@ mod tests {
         let mut body = response.into_body().collect().await.unwrap().to_bytes();
         let mut string_body = std::str::from_utf8(&body).unwrap();
         assert_eq!(
-            string_body,
-            "{\"id\":1,\"title\":\"test-title\",\"content\":\"test-content\"}"
+            without_timestamps(string_body),
+            json!({"id":1,"title":"test-title","content":"test-content","completed":false,"completed_at":null,"due_at":null,"list_id":null,"position":1024,"tags":[],"progress":{"done":0,"total":0}})
         );
 
         response = todo_router
             .clone()
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/")
                     .method("GET")
                     .body(Body::empty())
@@ -338,18 +407,22 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
         body = response.into_body().collect().await.unwrap().to_bytes();
         string_body = std::str::from_utf8(&body).unwrap();
-        assert_eq!(string_body, "{\"limit\":10,\"offset\":0,\"total\":1,\"items\":[{\"id\":1,\"title\":\"test-title\",\"content\":\"test-content\"}]}");
+        assert_eq!(
+            without_timestamps(string_body),
+            json!({"limit":10,"offset":0,"total":1,"items":[{"id":1,"title":"test-title","content":"test-content","completed":false,"completed_at":null,"due_at":null,"list_id":null,"position":1024,"tags":[],"progress":{"done":0,"total":0}}]})
+        );
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures("mock_users"))]
     async fn create_and_update_ok(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let mut response = todo_router
             .clone()
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/")
                     .method("POST")
                     .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
@@ -366,14 +439,15 @@ mod tests {
         let mut body = response.into_body().collect().await.unwrap().to_bytes();
         let mut string_body = std::str::from_utf8(&body).unwrap();
         assert_eq!(
-            string_body,
-            "{\"id\":1,\"title\":\"test-title\",\"content\":\"test-content\"}"
+            without_timestamps(string_body),
+            json!({"id":1,"title":"test-title","content":"test-content","completed":false,"completed_at":null,"due_at":null,"list_id":null,"position":1024,"tags":[],"progress":{"done":0,"total":0}})
         );
 
         response = todo_router
             .clone()
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/1")
                     .method("PUT")
                     .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
@@ -389,19 +463,20 @@ mod tests {
         body = response.into_body().collect().await.unwrap().to_bytes();
         string_body = std::str::from_utf8(&body).unwrap();
         assert_eq!(
-            string_body,
-            "{\"id\":1,\"title\":\"updated-test-title\",\"content\":\"updated-test-content\"}"
+            without_timestamps(string_body),
+            json!({"id":1,"title":"updated-test-title","content":"updated-test-content","completed":false,"completed_at":null,"due_at":null,"list_id":null,"position":1024,"tags":[],"progress":{"done":0,"total":0}})
         );
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures("mock_users"))]
     async fn update_err_non_numeric_id(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let response = todo_router
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/foobar")
                     .method("PUT")
                     .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
@@ -419,14 +494,15 @@ mod tests {
         assert_eq!(string_body, "{\"code\":400,\"message\":\"type of the following path is invalid\",\"path\":\"id\",\"comment\":\"expected type: interger\"}");
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures("mock_users"))]
     async fn update_err_not_found(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let response = todo_router
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/9999")
                     .method("PUT")
                     .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
@@ -444,15 +520,16 @@ mod tests {
         assert_eq!(string_body, "{\"code\":404,\"message\":\"not found\"}");
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures("mock_users"))]
     async fn create_and_delete_find_not_found(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let mut response = todo_router
             .clone()
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/")
                     .method("POST")
                     .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
@@ -469,14 +546,15 @@ mod tests {
         let mut body = response.into_body().collect().await.unwrap().to_bytes();
         let mut string_body = std::str::from_utf8(&body).unwrap();
         assert_eq!(
-            string_body,
-            "{\"id\":1,\"title\":\"test-title\",\"content\":\"test-content\"}"
+            without_timestamps(string_body),
+            json!({"id":1,"title":"test-title","content":"test-content","completed":false,"completed_at":null,"due_at":null,"list_id":null,"position":1024,"tags":[],"progress":{"done":0,"total":0}})
         );
 
         response = todo_router
             .clone()
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/1")
                     .method("DELETE")
                     .body(Body::empty())
@@ -494,6 +572,7 @@ mod tests {
             .clone()
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/1")
                     .method("GET")
                     .body(Body::empty())
@@ -508,14 +587,15 @@ mod tests {
         assert_eq!(string_body, "{\"code\":404,\"message\":\"not found\"}");
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures("mock_users"))]
     async fn delete_err_non_numeric_id(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let response = todo_router
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/foobar")
                     .method("DELETE")
                     .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
@@ -533,14 +613,15 @@ mod tests {
         assert_eq!(string_body, "{\"code\":400,\"message\":\"type of the following path is invalid\",\"path\":\"id\",\"comment\":\"expected type: interger\"}");
     }
 
-    #[sqlx::test]
+    #[sqlx::test(fixtures("mock_users"))]
     async fn delete_err_not_found(pg_pool: PgPool) {
-        let test_app_state = Arc::new(crate::configs::state::AppState { db_pool: pg_pool });
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
         let todo_router = todos_router().with_state(test_app_state);
 
         let response = todo_router
             .oneshot(
                 Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
                     .uri("/9999")
                     .method("DELETE")
                     .body(Body::empty())
@@ -554,4 +635,2189 @@ mod tests {
         let string_body = std::str::from_utf8(&body).unwrap();
         assert_eq!(string_body, "{\"code\":404,\"message\":\"not found\"}");
     }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn create_err_malformed_json(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let response = todo_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/")
+                    .method("POST")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .body(Body::from("{\"title\":\"test-title\","))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let string_body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(string_body, "{\"code\":400,\"message\":\"request body is not valid json\",\"path\":\"body\",\"comment\":\"EOF while parsing a value at line 1 column 22\"}");
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn create_err_missing_field(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let response = todo_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/")
+                    .method("POST")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .body(Body::from("{\"content\":\"test-content\"}"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let string_body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(string_body, "{\"code\":400,\"message\":\"the following field is missing\",\"path\":\"title\",\"comment\":\"field is required\"}");
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn create_err_invalid_field_type(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let response = todo_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/")
+                    .method("POST")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .body(Body::from("{\"title\":1,\"content\":\"test-content\"}"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let string_body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(string_body, "{\"code\":400,\"message\":\"type of the following field is invalid\",\"path\":\"title\",\"comment\":\"invalid type: integer `1`, expected a string\"}");
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn create_err_missing_content_type(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let response = todo_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/")
+                    .method("POST")
+                    .header(http::header::CONTENT_TYPE, mime::TEXT_PLAIN.as_ref())
+                    .body(Body::from(
+                        "{\"title\":\"test-title\",\"content\":\"test-content\"}",
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let string_body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(string_body, "{\"code\":415,\"message\":\"content type of the request is not supported\",\"path\":\"content-type\",\"comment\":\"expected: application/json\"}");
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn update_err_missing_field(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let response = todo_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/1")
+                    .method("PUT")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .body(Body::from("{\"title\":\"updated-test-title\"}"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let string_body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(string_body, "{\"code\":400,\"message\":\"the following field is missing\",\"path\":\"content\",\"comment\":\"field is required\"}");
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn create_err_validation(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let response = todo_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/")
+                    .method("POST")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .body(Body::from(format!(
+                        "{{\"title\":\"  \",\"content\":\"{}\"}}",
+                        "a".repeat(10_001)
+                    )))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let string_body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(string_body, "{\"code\":422,\"message\":\"the following fields are invalid\",\"errors\":[{\"field\":\"title\",\"reason\":\"must not be blank\"},{\"field\":\"content\",\"reason\":\"must be at most 10000 characters\"}]}");
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn update_err_validation(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let response = todo_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/1")
+                    .method("PUT")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .body(Body::from("{\"title\":\"line\\nbreak\",\"content\":\"test-content\"}"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let string_body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(string_body, "{\"code\":422,\"message\":\"the following fields are invalid\",\"errors\":[{\"field\":\"title\",\"reason\":\"must only contain printable characters\"}]}");
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn create_completed_with_due_date_ok(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let response = todo_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/")
+                    .method("POST")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .body(Body::from(
+                        "{\"title\":\"test-title\",\"content\":\"test-content\",\"completed\":true,\"due_at\":\"2030-01-01T09:00:00Z\"}",
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["completed"], json!(true));
+        assert_eq!(value["due_at"], json!("2030-01-01T09:00:00Z"));
+        assert!(value["completed_at"].is_string());
+        assert!(value["created_at"].is_string());
+        assert_eq!(value["created_at"], value["updated_at"]);
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_todo_with_due_date"))]
+    async fn patch_ok(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let mut response = todo_router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/1")
+                    .method("PATCH")
+                    .header(http::header::CONTENT_TYPE, "application/merge-patch+json")
+                    .body(Body::from("{\"completed\":true}"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut body = response.into_body().collect().await.unwrap().to_bytes();
+        let mut value: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["title"], json!("mock-title"));
+        assert_eq!(value["content"], json!("mock-content"));
+        assert_eq!(value["completed"], json!(true));
+        assert!(value["completed_at"].is_string());
+        assert_eq!(value["due_at"], json!("2030-01-01T09:00:00Z"));
+
+        response = todo_router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/1")
+                    .method("PATCH")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .body(Body::from("{\"title\":\"patched-title\",\"due_at\":null}"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        body = response.into_body().collect().await.unwrap().to_bytes();
+        value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["title"], json!("patched-title"));
+        assert_eq!(value["content"], json!("mock-content"));
+        assert_eq!(value["completed"], json!(true));
+        assert_eq!(value["due_at"], Value::Null);
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn patch_err_not_found(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let response = todo_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/9999")
+                    .method("PATCH")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .body(Body::from("{\"completed\":true}"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let string_body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(string_body, "{\"code\":404,\"message\":\"not found\"}");
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn patch_err_null_required_field(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let response = todo_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/1")
+                    .method("PATCH")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .body(Body::from("{\"title\":null,\"content\":\"ok\",\"completed\":null}"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let string_body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(string_body, "{\"code\":422,\"message\":\"the following fields are invalid\",\"errors\":[{\"field\":\"title\",\"reason\":\"must not be null\"},{\"field\":\"completed\",\"reason\":\"must not be null\"}]}");
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_filterable_todos"))]
+    async fn list_with_filter_and_sort_ok(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let response = todo_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/?q=buy&completed=false&sort=due_at:desc")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["total"], json!(1));
+        assert_eq!(value["items"][0]["title"], json!("buy milk"));
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn list_err_invalid_sort(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let response = todo_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/?sort=content:sideways")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let string_body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(string_body, "{\"code\":400,\"message\":\"the following query parameter is invalid\",\"path\":\"sort\",\"comment\":\"expected: <field>:<asc|desc> with field one of position, id, title, completed, due_at, created_at, updated_at\"}");
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn list_err_invalid_completed(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let response = todo_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/?completed=maybe")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["path"], json!("completed"));
+        assert_eq!(value["message"], json!("the following query parameter is invalid"));
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_filterable_todos"))]
+    async fn list_with_cursor_ok(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let mut titles: Vec<String> = vec![];
+        let mut uri = "/?limit=3&sort=due_at:desc&pagination=cursor".to_string();
+        loop {
+            let response = todo_router
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .header(http::header::AUTHORIZATION, bearer(ALICE))
+                        .uri(uri.as_str())
+                        .method("GET")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let value: Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(value["total"], json!(4));
+            for item in value["items"].as_array().unwrap() {
+                titles.push(item["title"].as_str().unwrap().to_string());
+            }
+            match value["next_cursor"].as_str() {
+                None => break,
+                Some(cursor) => uri = format!("/?limit=3&sort=due_at:desc&cursor={}", cursor),
+            }
+        }
+
+        assert_eq!(titles, vec!["buy milk", "buy flowers", "write report", "call mom"]);
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_filterable_todos"))]
+    async fn list_without_total_ok(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let response = todo_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/?limit=1&offset=1&include_total=false")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value.get("total"), None);
+        assert_eq!(value["offset"], json!(1));
+        assert_eq!(value["items"][0]["title"], json!("write report"));
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn list_err_invalid_cursor(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let mut response = todo_router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/?cursor=garbage")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let mut body = response.into_body().collect().await.unwrap().to_bytes();
+        let mut string_body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(string_body, "{\"code\":400,\"message\":\"the following query parameter is invalid\",\"path\":\"cursor\",\"comment\":\"cursor is malformed or was issued for a different sort\"}");
+
+        response = todo_router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/?pagination=cursor&offset=10")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        body = response.into_body().collect().await.unwrap().to_bytes();
+        string_body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(string_body, "{\"code\":400,\"message\":\"the following query parameter is invalid\",\"path\":\"offset\",\"comment\":\"offset cannot be combined with cursor\"}");
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_filterable_todos"))]
+    async fn list_with_configured_page_size_ok(pg_pool: PgPool) {
+        let mut state = crate::configs::state::AppState::new(pg_pool);
+        state.settings.pagination.modules.insert(
+            "todos".to_string(),
+            crate::configs::pagination::PageSizeLimits::new(2, 3).unwrap(),
+        );
+        let todo_router = todos_router().with_state(Arc::new(state));
+
+        let mut response = todo_router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut body = response.into_body().collect().await.unwrap().to_bytes();
+        let mut value: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["limit"], json!(2));
+        assert_eq!(value.get("requested_limit"), None);
+        assert_eq!(value["items"].as_array().unwrap().len(), 2);
+
+        response = todo_router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/?limit=4")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        body = response.into_body().collect().await.unwrap().to_bytes();
+        value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["limit"], json!(3));
+        assert_eq!(value["requested_limit"], json!(4));
+        assert_eq!(value["items"].as_array().unwrap().len(), 3);
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn list_err_invalid_limit_offset(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let cases = [
+            ("/?limit=-1", "{\"code\":400,\"message\":\"the following query parameter is invalid\",\"path\":\"limit\",\"comment\":\"must be a positive integer\"}"),
+            ("/?offset=-5", "{\"code\":400,\"message\":\"the following query parameter is invalid\",\"path\":\"offset\",\"comment\":\"must not be negative\"}"),
+            ("/?limit=ten", "{\"code\":400,\"message\":\"the following query parameter is invalid\",\"path\":\"limit\",\"comment\":\"invalid digit found in string\"}"),
+        ];
+        for (uri, expected_body) in cases {
+            let response = todo_router
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .header(http::header::AUTHORIZATION, bearer(ALICE))
+                        .uri(uri)
+                        .method("GET")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let string_body = std::str::from_utf8(&body).unwrap();
+            assert_eq!(string_body, expected_body);
+        }
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_todo_with_due_date"))]
+    async fn todos_err_unauthorized(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let keys = test_app_state.jwt.clone();
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let mut expired_claims = keys.claims_for(ALICE);
+        expired_claims.exp = expired_claims.iat - 3600;
+        let mut foreign_claims = keys.claims_for(ALICE);
+        foreign_claims.aud = "another-service".to_string();
+
+        let missing_credentials = "{\"code\":401,\"message\":\"authentication is required\",\"reason\":\"missing_credentials\"}";
+        let invalid_token = "{\"code\":401,\"message\":\"access token is invalid\",\"reason\":\"invalid_token\"}";
+        let expired_token = "{\"code\":401,\"message\":\"access token has expired\",\"reason\":\"expired_token\"}";
+        let cases = [
+            (None, missing_credentials.to_string()),
+            (
+                Some("Basic YWxpY2VAZXhhbXBsZS5jb206cGFzc3dvcmQxMjM=".to_string()),
+                missing_credentials.to_string(),
+            ),
+            (Some("Bearer not-a-token".to_string()), invalid_token.to_string()),
+            (
+                Some(format!("Bearer {}", keys.encode(&foreign_claims).unwrap())),
+                invalid_token.to_string(),
+            ),
+            (
+                Some(format!("Bearer {}", keys.encode(&expired_claims).unwrap())),
+                expired_token.to_string(),
+            ),
+        ];
+        for (authorization, expected_body) in cases {
+            let mut request = Request::builder().uri("/1").method("GET");
+            if let Some(authorization) = authorization {
+                request = request.header(http::header::AUTHORIZATION, authorization);
+            }
+            let response = todo_router
+                .clone()
+                .oneshot(request.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            assert_eq!(response.headers()[http::header::WWW_AUTHENTICATE], "Bearer, ApiKey");
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let string_body = std::str::from_utf8(&body).unwrap();
+            assert_eq!(string_body, expected_body);
+        }
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_todo_with_due_date"))]
+    async fn todos_err_foreign_todo_not_found(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let todo_router = todos_router().with_state(test_app_state);
+
+        let cases = [
+            ("GET", ""),
+            ("PUT", "{\"title\":\"stolen\",\"content\":\"stolen\"}"),
+            ("PATCH", "{\"title\":\"stolen\"}"),
+            ("DELETE", ""),
+        ];
+        for (method, request_body) in cases {
+            let response = todo_router
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .header(http::header::AUTHORIZATION, bearer(BOB))
+                        .uri("/1")
+                        .method(method)
+                        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                        .body(Body::from(request_body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+
+        let mut response = todo_router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(BOB))
+                    .uri("/")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut body = response.into_body().collect().await.unwrap().to_bytes();
+        let mut value: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["total"], json!(0));
+        assert_eq!(value["items"], json!([]));
+
+        response = todo_router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(ALICE))
+                    .uri("/1")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        body = response.into_body().collect().await.unwrap().to_bytes();
+        value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["title"], json!("mock-title"));
+    }
+
+    fn without_latencies(body: &[u8]) -> Value {
+        let mut value: Value = serde_json::from_slice(body).unwrap();
+        if let Some(checks) = value["checks"].as_object_mut() {
+            for check in checks.values_mut() {
+                check.as_object_mut().unwrap().remove("latency_ms");
+            }
+        }
+        value
+    }
+
+    #[sqlx::test]
+    async fn healthz_ok(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let health_router = health_router().with_state(test_app_state);
+
+        let response = health_router
+            .oneshot(
+                Request::builder()
+                    .uri("/healthz")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let string_body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(string_body, "{\"status\":\"ok\"}");
+    }
+
+    #[sqlx::test]
+    async fn readyz_ok(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let health_router = health_router().with_state(test_app_state);
+
+        let response = health_router
+            .oneshot(
+                Request::builder()
+                    .uri("/readyz")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: Value = serde_json::from_slice(&body).unwrap();
+        assert!(value["checks"]["database"]["latency_ms"].is_u64());
+        assert_eq!(
+            without_latencies(&body),
+            json!({
+                "status": "ok",
+                "checks": {
+                    "database": {"status": "ok"},
+                    "migrations": {"status": "ok"}
+                }
+            })
+        );
+    }
+
+    #[sqlx::test(migrations = false)]
+    async fn readyz_err_pending_migrations(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool.clone()));
+        let health_router = health_router().with_state(test_app_state);
+
+        let response = health_router
+            .oneshot(
+                Request::builder()
+                    .uri("/readyz")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let first_version = crate::configs::migrations::MIGRATOR
+            .iter()
+            .map(|migration| migration.version)
+            .min()
+            .unwrap();
+        assert_eq!(
+            without_latencies(&body),
+            json!({
+                "status": "unavailable",
+                "checks": {
+                    "database": {"status": "ok"},
+                    "migrations": {
+                        "status": "unavailable",
+                        "error": format!("migration {} is pending", first_version)
+                    }
+                }
+            })
+        );
+        let has_table =
+            sqlx::query_scalar::<_, bool>("SELECT to_regclass('_sqlx_migrations') IS NOT NULL;")
+                .fetch_one(&pg_pool)
+                .await
+                .unwrap();
+        assert!(!has_table);
+    }
+
+    #[tokio::test]
+    async fn readyz_err_database_hides_details() {
+        let pg_pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_millis(500))
+            .connect_lazy("postgres://secret_role@127.0.0.1:1/secret_database")
+            .unwrap();
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let health_router = health_router().with_state(test_app_state);
+
+        let response = health_router
+            .oneshot(
+                Request::builder()
+                    .uri("/readyz")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(
+            without_latencies(&body)["checks"]["database"],
+            json!({"status": "unavailable", "error": "unavailable"})
+        );
+        assert!(!std::str::from_utf8(&body).unwrap().contains("secret"));
+    }
+
+    #[sqlx::test]
+    async fn readyz_err_shutting_down(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        test_app_state.mark_not_ready();
+        let health_router = health_router().with_state(test_app_state);
+
+        let response = health_router
+            .oneshot(
+                Request::builder()
+                    .uri("/readyz")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(
+            without_latencies(&body),
+            json!({
+                "status": "unavailable",
+                "checks": {
+                    "database": {"status": "ok"},
+                    "migrations": {"status": "ok"},
+                    "shutdown": {
+                        "status": "unavailable",
+                        "error": "server is shutting down"
+                    }
+                }
+            })
+        );
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn register_ok(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let users_router = users_router().with_state(test_app_state);
+
+        let response = users_router
+            .oneshot(
+                Request::builder()
+                    .uri("/")
+                    .method("POST")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .body(Body::from(
+                        "{\"email\":\" Carol@Example.com \",\"password\":\"correct horse\"}",
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(
+            without_timestamps(std::str::from_utf8(&body).unwrap()),
+            json!({"id": 3, "email": "carol@example.com", "role": "user", "disabled_at": null})
+        );
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn register_err(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let users_router = users_router().with_state(test_app_state);
+
+        let cases = [
+            (
+                "{\"email\":\"ALICE@example.com\",\"password\":\"password123\"}",
+                StatusCode::CONFLICT,
+                "{\"code\":409,\"message\":\"conflict\"}",
+            ),
+            (
+                "{\"email\":\"carol\",\"password\":\"short\"}",
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "{\"code\":422,\"message\":\"the following fields are invalid\",\"errors\":[{\"field\":\"email\",\"reason\":\"must be a valid email address\"},{\"field\":\"password\",\"reason\":\"must be at least 8 characters\"}]}",
+            ),
+            (
+                "{\"email\":\"carol@example.com\"}",
+                StatusCode::BAD_REQUEST,
+                "{\"code\":400,\"message\":\"the following field is missing\",\"path\":\"password\",\"comment\":\"field is required\"}",
+            ),
+        ];
+        for (request_body, status, expected_body) in cases {
+            let response = users_router
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri("/")
+                        .method("POST")
+                        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                        .body(Body::from(request_body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), status);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let string_body = std::str::from_utf8(&body).unwrap();
+            assert_eq!(string_body, expected_body);
+        }
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn me_ok(pg_pool: PgPool) {
+        let test_app_state = Arc::new(crate::configs::state::AppState::new(pg_pool));
+        let users_router = users_router().with_state(test_app_state);
+
+        let response = users_router
+            .oneshot(
+                Request::builder()
+                    .header(http::header::AUTHORIZATION, bearer(BOB))
+                    .uri("/me")
+                    .method("GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(
+            without_timestamps(std::str::from_utf8(&body).unwrap()),
+            json!({"id": 2, "email": "bob@example.com", "role": "user", "disabled_at": null})
+        );
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn login_ok(pg_pool: PgPool) {
+        let keys = JwtKeys::new(&Settings::default().auth).unwrap();
+        let router = app(pg_pool);
+
+        let (status, _, value) = send(
+            &router,
+            "POST",
+            "/auth/login",
+            "",
+            &[],
+            Some(json!({"email": "Alice@Example.com", "password": "password123"})),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(value["token_type"], json!("Bearer"));
+        assert_eq!(value["expires_in"], json!(900));
+        assert!(value["refresh_token"].is_string());
+        let claims = keys.verify(value["access_token"].as_str().unwrap()).unwrap();
+        assert_eq!(claims.sub, "1");
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn login_err_invalid_credentials(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        let cases = [
+            json!({"email": "alice@example.com", "password": "wrong-password"}),
+            json!({"email": "nobody@example.com", "password": "password123"}),
+        ];
+        for request_body in cases {
+            let (status, _, value) =
+                send(&router, "POST", "/auth/login", "", &[], Some(request_body)).await;
+
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+            assert_eq!(
+                value,
+                json!({
+                    "code": 401,
+                    "message": "email or password is incorrect",
+                    "reason": "invalid_credentials"
+                })
+            );
+        }
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn refresh_ok_rotates_token(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        let (_, _, login) = send(
+            &router,
+            "POST",
+            "/auth/login",
+            "",
+            &[],
+            Some(json!({"email": "bob@example.com", "password": "password123"})),
+        )
+        .await;
+        let first_token = login["refresh_token"].as_str().unwrap().to_string();
+
+        let (status, _, refreshed) = send(
+            &router,
+            "POST",
+            "/auth/refresh",
+            "",
+            &[],
+            Some(json!({"refresh_token": first_token})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let second_token = refreshed["refresh_token"].as_str().unwrap().to_string();
+        assert_ne!(first_token, second_token);
+        assert!(refreshed["access_token"].is_string());
+
+        // Replaying the rotated token revokes every token of the session.
+        let (status, _, value) = send(
+            &router,
+            "POST",
+            "/auth/refresh",
+            "",
+            &[],
+            Some(json!({"refresh_token": first_token})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            value,
+            json!({
+                "code": 401,
+                "message": "refresh token is invalid, expired or revoked",
+                "reason": "invalid_refresh_token"
+            })
+        );
+        let (status, _, _) = send(
+            &router,
+            "POST",
+            "/auth/refresh",
+            "",
+            &[],
+            Some(json!({"refresh_token": second_token})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn logout_ok(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        let (_, _, login) = send(
+            &router,
+            "POST",
+            "/auth/login",
+            "",
+            &[],
+            Some(json!({"email": "bob@example.com", "password": "password123"})),
+        )
+        .await;
+        let refresh_token = json!({"refresh_token": login["refresh_token"]});
+
+        let (status, _, _) =
+            send(&router, "POST", "/auth/logout", "", &[], Some(refresh_token.clone())).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _, _) =
+            send(&router, "POST", "/auth/refresh", "", &[], Some(refresh_token.clone())).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _, _) =
+            send(&router, "POST", "/auth/logout", "", &[], Some(refresh_token)).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+    }
+
+    async fn send(
+        router: &axum::Router,
+        method: &str,
+        uri: &str,
+        authorization: &str,
+        headers: &[(http::HeaderName, &str)],
+        body: Option<Value>,
+    ) -> (StatusCode, http::HeaderMap, Value) {
+        let mut request = Request::builder().uri(uri).method(method);
+        if !authorization.is_empty() {
+            request = request.header(http::header::AUTHORIZATION, authorization);
+        }
+        for (name, value) in headers {
+            request = request.header(name, *value);
+        }
+        if body.is_some() {
+            request = request.header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref());
+        }
+        let response = router
+            .clone()
+            .oneshot(
+                request
+                    .body(body.map_or(Body::empty(), |body| Body::from(body.to_string())))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, headers, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    fn app(pg_pool: PgPool) -> axum::Router {
+        app_router(Arc::new(crate::configs::state::AppState::new(pg_pool)))
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn api_keys_ok(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        let (status, _, created) = send(
+            &router,
+            "POST",
+            "/api-keys",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"name": "ci", "scope": "read_write"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let key = created["key"].as_str().unwrap().to_string();
+        assert!(key.starts_with("tdk_"));
+        assert_eq!(created["prefix"], json!(key[..12]));
+        assert_eq!(created["name"], json!("ci"));
+        assert_eq!(created["scope"], json!("read_write"));
+        assert_eq!(created["last_used_at"], Value::Null);
+
+        let api_key = format!("ApiKey {}", key);
+        let (status, _, todo) = send(
+            &router,
+            "POST",
+            "/todos",
+            &api_key,
+            &[],
+            Some(json!({"title": "from ci", "content": "nightly build"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let uri = format!("/todos/{}", todo["id"]);
+        let (status, _, _) = send(&router, "GET", &uri, &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _, renamed) = send(
+            &router,
+            "PATCH",
+            "/api-keys/1",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"name": "nightly ci"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(renamed["name"], json!("nightly ci"));
+
+        let (status, _, listed) =
+            send(&router, "GET", "/api-keys", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+        let listed = listed.as_array().unwrap();
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0]["last_used_at"].is_string());
+        assert!(listed[0].get("key").is_none());
+
+        let (status, _, _) =
+            send(&router, "DELETE", "/api-keys/1", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _, value) = send(&router, "GET", "/todos", &api_key, &[], None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            value,
+            json!({"code": 401, "message": "api key is invalid or revoked", "reason": "invalid_api_key"})
+        );
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn api_keys_err_scope(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        let (_, _, created) = send(
+            &router,
+            "POST",
+            "/api-keys",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"name": "dashboard", "scope": "read_only"})),
+        )
+        .await;
+        let api_key = format!("ApiKey {}", created["key"].as_str().unwrap());
+
+        let (status, _, _) = send(&router, "GET", "/todos", &api_key, &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let forbidden = json!({"code": 403, "message": "forbidden"});
+        let cases = [
+            ("POST", "/todos", Some(json!({"title": "t", "content": "c"}))),
+            ("DELETE", "/todos/1", None),
+            ("GET", "/api-keys", None),
+            ("POST", "/api-keys", Some(json!({"name": "escalated", "scope": "read_write"}))),
+            ("DELETE", "/api-keys/1", None),
+        ];
+        for (method, uri, body) in cases {
+            let (status, _, value) = send(&router, method, uri, &api_key, &[], body).await;
+            assert_eq!(status, StatusCode::FORBIDDEN);
+            assert_eq!(value, forbidden);
+        }
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn api_keys_err(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        send(
+            &router,
+            "POST",
+            "/api-keys",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"name": "ci", "scope": "read_write"})),
+        )
+        .await;
+
+        let (status, _, _) = send(&router, "DELETE", "/api-keys/1", &bearer(BOB), &[], None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _, listed) = send(&router, "GET", "/api-keys", &bearer(BOB), &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(listed, json!([]));
+
+        let (status, _, value) = send(
+            &router,
+            "POST",
+            "/api-keys",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"name": " ", "scope": "admin"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(value["path"], json!("scope"));
+
+        let (status, _, value) = send(
+            &router,
+            "POST",
+            "/api-keys",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"name": " ", "scope": "read_only"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(value["errors"], json!([{"field": "name", "reason": "must not be blank"}]));
+
+        let (status, _, _) = send(&router, "GET", "/todos", "ApiKey tdk_unknown", &[], None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_admin"))]
+    async fn admin_ok(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        let (status, _, users) =
+            send(&router, "GET", "/admin/users?limit=1", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(users["total"], json!(2));
+        assert_eq!(users["items"].as_array().unwrap().len(), 1);
+        assert_eq!(users["items"][0]["role"], json!("admin"));
+
+        let (_, _, todo) = send(
+            &router,
+            "POST",
+            "/todos",
+            &bearer(BOB),
+            &[],
+            Some(json!({"title": "bob's", "content": "private"})),
+        )
+        .await;
+        let uri = format!("/admin/todos/{}", todo["id"]);
+        let (status, _, value) = send(&router, "GET", &uri, &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(value["title"], json!("bob's"));
+        assert_eq!(value["owner_id"], json!(BOB));
+
+        let (status, _, disabled) =
+            send(&router, "POST", "/admin/users/2/disable", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(disabled["disabled_at"].is_string());
+        let (status, _, value) = send(&router, "GET", "/todos", &bearer(BOB), &[], None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            value,
+            json!({"code": 401, "message": "account is disabled", "reason": "account_disabled"})
+        );
+        let (status, _, value) = send(
+            &router,
+            "POST",
+            "/auth/login",
+            "",
+            &[],
+            Some(json!({"email": "bob@example.com", "password": "password123"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(value["reason"], json!("account_disabled"));
+
+        let (status, _, enabled) =
+            send(&router, "POST", "/admin/users/2/enable", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(enabled["disabled_at"], Value::Null);
+        let (status, _, _) = send(&router, "GET", "/todos", &bearer(BOB), &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_admin"))]
+    async fn admin_err(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        let forbidden = json!({"code": 403, "message": "forbidden"});
+        for uri in ["/admin/users", "/admin/todos/1"] {
+            let (status, _, value) = send(&router, "GET", uri, &bearer(BOB), &[], None).await;
+            assert_eq!(status, StatusCode::FORBIDDEN);
+            assert_eq!(value, forbidden);
+        }
+        let (status, _, _) =
+            send(&router, "POST", "/admin/users/1/disable", &bearer(BOB), &[], None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _, _) =
+            send(&router, "POST", "/admin/users/1/disable", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (_, _, created) = send(
+            &router,
+            "POST",
+            "/api-keys",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"name": "ci", "scope": "read_write"})),
+        )
+        .await;
+        let api_key = format!("ApiKey {}", created["key"].as_str().unwrap());
+        let (status, _, value) = send(&router, "GET", "/admin/users", &api_key, &[], None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(value, forbidden);
+
+        let (status, _, _) =
+            send(&router, "POST", "/admin/users/99/disable", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _, _) =
+            send(&router, "GET", "/admin/todos/99", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn lists_ok(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        let (status, _, work) = send(
+            &router,
+            "POST",
+            "/lists",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"name": "work", "color": "#1E90FF"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(work["color"], json!("#1e90ff"));
+        assert_eq!(work["archived"], json!(false));
+        let (_, _, home) = send(
+            &router,
+            "POST",
+            "/lists",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"name": "home"})),
+        )
+        .await;
+
+        for (title, completed) in [("write report", false), ("book flights", true)] {
+            let (status, _, todo) = send(
+                &router,
+                "POST",
+                "/lists/1/todos",
+                &bearer(ALICE),
+                &[],
+                Some(json!({"title": title, "content": "", "completed": completed})),
+            )
+            .await;
+            assert_eq!(status, StatusCode::CREATED);
+            assert_eq!(todo["list_id"], work["id"]);
+        }
+        send(
+            &router,
+            "POST",
+            "/todos",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"title": "unsorted", "content": ""})),
+        )
+        .await;
+
+        let (status, _, todos) =
+            send(&router, "GET", "/lists/1/todos", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(todos["total"], json!(2));
+        let (_, _, todos) = send(
+            &router,
+            "GET",
+            "/todos?list_id=1&completed=true",
+            &bearer(ALICE),
+            &[],
+            None,
+        )
+        .await;
+        assert_eq!(todos["items"][0]["title"], json!("book flights"));
+
+        let (status, _, list) = send(&router, "GET", "/lists/1", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!((&list["open_count"], &list["completed_count"]), (&json!(1), &json!(1)));
+
+        let (status, _, patched) = send(
+            &router,
+            "PATCH",
+            "/lists/2",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"archived": true, "color": null})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(patched["archived"], json!(true));
+        assert_eq!(patched["name"], home["name"]);
+        let (_, _, lists) =
+            send(&router, "GET", "/lists?archived=false", &bearer(ALICE), &[], None).await;
+        assert_eq!(lists["total"], json!(1));
+        assert_eq!(lists["items"][0]["name"], json!("work"));
+
+        let (status, _, _) =
+            send(&router, "DELETE", "/lists/1?reassign_to=2", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (_, _, list) = send(&router, "GET", "/lists/2", &bearer(ALICE), &[], None).await;
+        assert_eq!((&list["open_count"], &list["completed_count"]), (&json!(1), &json!(1)));
+
+        let (status, _, _) = send(&router, "DELETE", "/lists/2", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (_, _, todos) = send(&router, "GET", "/todos", &bearer(ALICE), &[], None).await;
+        assert_eq!(todos["total"], json!(1));
+        assert_eq!(todos["items"][0]["title"], json!("unsorted"));
+        let (_, _, trash) = send(&router, "GET", "/todos/trash", &bearer(ALICE), &[], None).await;
+        assert_eq!(trash["total"], json!(2));
+        assert!(trash["items"].as_array().unwrap().iter().all(|todo| todo["list_id"].is_null()));
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn lists_err(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        let (status, _, value) = send(
+            &router,
+            "POST",
+            "/lists",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"name": " ", "color": "blue"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            value["errors"],
+            json!([
+                {"field": "name", "reason": "must not be blank"},
+                {"field": "color", "reason": "must be a hex color like #1e90ff"}
+            ])
+        );
+
+        send(&router, "POST", "/lists", &bearer(ALICE), &[], Some(json!({"name": "work"}))).await;
+        for (method, uri) in [("GET", "/lists/1"), ("GET", "/lists/1/todos"), ("DELETE", "/lists/1")] {
+            let (status, _, _) = send(&router, method, uri, &bearer(BOB), &[], None).await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+        }
+        let (status, _, value) = send(
+            &router,
+            "POST",
+            "/todos",
+            &bearer(BOB),
+            &[],
+            Some(json!({"title": "sneaky", "content": "", "list_id": 1})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            value["errors"],
+            json!([{"field": "list_id", "reason": "must reference one of your lists"}])
+        );
+
+        for uri in ["/lists/1?reassign_to=1", "/lists/1?reassign_to=99"] {
+            let (status, _, value) = send(&router, "DELETE", uri, &bearer(ALICE), &[], None).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(value["path"], json!("reassign_to"));
+        }
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn tags_ok(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        for title in ["report", "invoice"] {
+            send(
+                &router,
+                "POST",
+                "/todos",
+                &bearer(ALICE),
+                &[],
+                Some(json!({"title": title, "content": ""})),
+            )
+            .await;
+        }
+        for (id, name) in [(1, " Urgent "), (1, "work"), (2, "work")] {
+            let uri = format!("/todos/{}/tags", id);
+            let (status, _, _) =
+                send(&router, "POST", &uri, &bearer(ALICE), &[], Some(json!({"name": name}))).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        let (_, _, todo) = send(&router, "GET", "/todos/1", &bearer(ALICE), &[], None).await;
+        assert_eq!(todo["tags"], json!(["urgent", "work"]));
+
+        let (status, _, tags) = send(&router, "GET", "/tags", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            tags,
+            json!([{"id": 1, "name": "urgent", "usage_count": 1}, {"id": 2, "name": "work", "usage_count": 2}])
+        );
+
+        let (_, _, todos) =
+            send(&router, "GET", "/todos?tag=work&tag=urgent", &bearer(ALICE), &[], None).await;
+        assert_eq!(todos["total"], json!(2));
+        let (_, _, todos) = send(
+            &router,
+            "GET",
+            "/todos?tag=work&tag=URGENT&tag_match=all",
+            &bearer(ALICE),
+            &[],
+            None,
+        )
+        .await;
+        assert_eq!(todos["total"], json!(1));
+        assert_eq!(todos["items"][0]["title"], json!("report"));
+
+        let (status, _, _) =
+            send(&router, "DELETE", "/todos/1/tags/urgent", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _, _) =
+            send(&router, "DELETE", "/todos/1/tags/urgent", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (_, _, todo) = send(&router, "GET", "/todos/1", &bearer(ALICE), &[], None).await;
+        assert_eq!(todo["tags"], json!(["work"]));
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn tags_err(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        send(
+            &router,
+            "POST",
+            "/todos",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"title": "report", "content": ""})),
+        )
+        .await;
+
+        let (status, _, _) = send(
+            &router,
+            "POST",
+            "/todos/1/tags",
+            &bearer(BOB),
+            &[],
+            Some(json!({"name": "mine"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (_, _, tags) = send(&router, "GET", "/tags", &bearer(BOB), &[], None).await;
+        assert_eq!(tags, json!([]));
+
+        let (status, _, value) = send(
+            &router,
+            "POST",
+            "/todos/1/tags",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"name": " "})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(value["errors"], json!([{"field": "name", "reason": "must not be blank"}]));
+
+        let (status, _, value) =
+            send(&router, "GET", "/todos?tag=a&tag_match=some", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(value["path"], json!("tag_match"));
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn todo_items_ok(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        send(
+            &router,
+            "POST",
+            "/todos",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"title": "bake a cake", "content": ""})),
+        )
+        .await;
+        for title in ["eggs", "flour", "sugar"] {
+            let (status, _, item) = send(
+                &router,
+                "POST",
+                "/todos/1/items",
+                &bearer(ALICE),
+                &[],
+                Some(json!({"title": title})),
+            )
+            .await;
+            assert_eq!(status, StatusCode::CREATED);
+            assert_eq!(item["done"], json!(false));
+        }
+
+        let (status, _, item) = send(
+            &router,
+            "PATCH",
+            "/todos/1/items/2",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"done": true})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(item["title"], json!("flour"));
+        let (_, _, todo) = send(&router, "GET", "/todos/1", &bearer(ALICE), &[], None).await;
+        assert_eq!(todo["progress"], json!({"done": 1, "total": 3}));
+
+        let (status, _, items) = send(
+            &router,
+            "PUT",
+            "/todos/1/items",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"item_ids": [3, 2, 1]})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let order: Vec<&Value> = items.as_array().unwrap().iter().map(|item| &item["title"]).collect();
+        assert_eq!(order, [&json!("sugar"), &json!("flour"), &json!("eggs")]);
+
+        let (status, _, _) =
+            send(&router, "DELETE", "/todos/1/items/3", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (_, _, items) = send(&router, "GET", "/todos/1/items", &bearer(ALICE), &[], None).await;
+        assert_eq!(items[0]["title"], json!("flour"));
+        assert_eq!(items[0]["position"], json!(0));
+        let (_, _, todo) = send(&router, "GET", "/todos", &bearer(ALICE), &[], None).await;
+        assert_eq!(todo["items"][0]["progress"], json!({"done": 1, "total": 2}));
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn todo_items_err(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        send(
+            &router,
+            "POST",
+            "/todos",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"title": "bake a cake", "content": ""})),
+        )
+        .await;
+        send(
+            &router,
+            "POST",
+            "/todos/1/items",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"title": "eggs"})),
+        )
+        .await;
+
+        let (status, _, _) = send(&router, "GET", "/todos/1/items", &bearer(BOB), &[], None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _, _) = send(
+            &router,
+            "PATCH",
+            "/todos/1/items/1",
+            &bearer(BOB),
+            &[],
+            Some(json!({"done": true})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _, _) =
+            send(&router, "DELETE", "/todos/1/items/2", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _, value) = send(
+            &router,
+            "PATCH",
+            "/todos/1/items/1",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"done": null})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(value["errors"], json!([{"field": "done", "reason": "must not be null"}]));
+
+        for item_ids in [json!([]), json!([1, 1]), json!([1, 2])] {
+            let (status, _, value) = send(
+                &router,
+                "PUT",
+                "/todos/1/items",
+                &bearer(ALICE),
+                &[],
+                Some(json!({"item_ids": item_ids})),
+            )
+            .await;
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+            assert_eq!(value["errors"][0]["field"], json!("item_ids"));
+        }
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_filterable_todos"))]
+    async fn move_ok(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        let (status, _, todo) = send(
+            &router,
+            "POST",
+            "/todos/4/move",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"before": 1})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(todo["position"], json!(0));
+        let (status, _, todo) = send(
+            &router,
+            "POST",
+            "/todos/3/move",
+            &bearer(ALICE),
+            &[],
+            Some(json!({"after": 4})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(todo["position"], json!(512));
+
+        let (_, _, todos) = send(&router, "GET", "/todos", &bearer(ALICE), &[], None).await;
+        let ids: Vec<&Value> = todos["items"].as_array().unwrap().iter().map(|todo| &todo["id"]).collect();
+        assert_eq!(ids, [&json!(4), &json!(3), &json!(1), &json!(2)]);
+        let (_, _, todos) = send(
+            &router,
+            "GET",
+            "/todos?sort=position:desc&limit=1",
+            &bearer(ALICE),
+            &[],
+            None,
+        )
+        .await;
+        assert_eq!(todos["items"][0]["id"], json!(2));
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_filterable_todos"))]
+    async fn move_err(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        let cases = [
+            (json!({}), "before", "exactly one of before and after is required"),
+            (json!({"before": 2, "after": 3}), "before", "exactly one of before and after is required"),
+            (json!({"after": 1}), "after", "must reference another one of your todos"),
+            (json!({"before": 99}), "before", "must reference another one of your todos"),
+        ];
+        for (body, field, reason) in cases {
+            let (status, _, value) =
+                send(&router, "POST", "/todos/1/move", &bearer(ALICE), &[], Some(body)).await;
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+            assert_eq!(value["errors"], json!([{"field": field, "reason": reason}]));
+        }
+        let (status, _, _) = send(
+            &router,
+            "POST",
+            "/todos/1/move",
+            &bearer(BOB),
+            &[],
+            Some(json!({"before": 2})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_filterable_todos"))]
+    async fn trash_and_restore_ok(pg_pool: PgPool) {
+        let router = app(pg_pool);
+
+        let (status, _, _) = send(&router, "DELETE", "/todos/1", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _, _) = send(&router, "GET", "/todos/1", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _, trash) =
+            send(&router, "GET", "/todos/trash", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(trash["total"], json!(1));
+        assert_eq!(trash["items"][0]["id"], json!(1));
+        assert!(trash["items"][0]["deleted_at"].is_string());
+        let (_, _, trash) = send(&router, "GET", "/todos/trash", &bearer(BOB), &[], None).await;
+        assert_eq!(trash["items"], json!([]));
+
+        let (status, _, _) =
+            send(&router, "POST", "/todos/1/restore", &bearer(BOB), &[], None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _, todo) =
+            send(&router, "POST", "/todos/1/restore", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(todo["id"], json!(1));
+        assert!(todo.get("deleted_at").is_none());
+        let (status, _, _) =
+            send(&router, "POST", "/todos/1/restore", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _, _) = send(&router, "GET", "/todos/1", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn etag_ok(pg_pool: PgPool) {
+        let router = app(pg_pool);
+        let alice = bearer(ALICE);
+        let body = json!({"title": "draft", "content": ""});
+
+        let (status, headers, todo) =
+            send(&router, "POST", "/todos", &alice, &[], Some(body)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(headers[http::header::ETAG], "\"1\"");
+        let uri = format!("/todos/{}", todo["id"]);
+
+        let (status, headers, _) = send(&router, "GET", &uri, &alice, &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers[http::header::ETAG], "\"1\"");
+        let if_none_match = [(http::header::IF_NONE_MATCH, "\"1\"")];
+        let (status, headers, body) =
+            send(&router, "GET", &uri, &alice, &if_none_match, None).await;
+        assert_eq!(status, StatusCode::NOT_MODIFIED);
+        assert_eq!(headers[http::header::ETAG], "\"1\"");
+        assert_eq!(body, Value::Null);
+
+        let body = json!({"title": "final", "content": ""});
+        let if_match = [(http::header::IF_MATCH, "\"1\"")];
+        let (status, headers, todo) =
+            send(&router, "PUT", &uri, &alice, &if_match, Some(body)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers[http::header::ETAG], "\"2\"");
+        assert_eq!(todo["title"], json!("final"));
+        let (status, headers, _) = send(
+            &router,
+            "PATCH",
+            &uri,
+            &alice,
+            &[(http::header::IF_MATCH, "\"1\", \"2\"")],
+            Some(json!({"completed": true})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers[http::header::ETAG], "\"3\"");
+        let if_none_match = [(http::header::IF_NONE_MATCH, "\"2\"")];
+        let (status, _, _) = send(&router, "GET", &uri, &alice, &if_none_match, None).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let if_match = [(http::header::IF_MATCH, "*")];
+        let (status, _, _) = send(&router, "DELETE", &uri, &alice, &if_match, None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_filterable_todos"))]
+    async fn etag_err_precondition_failed(pg_pool: PgPool) {
+        let router = app(pg_pool);
+        let alice = bearer(ALICE);
+        let stale = [(http::header::IF_MATCH, "\"2\"")];
+        let body = json!({"title": "mine", "content": ""});
+
+        for (method, body) in [("PUT", Some(body.clone())), ("PATCH", Some(body)), ("DELETE", None)] {
+            let (status, _, value) = send(&router, method, "/todos/1", &alice, &stale, body).await;
+            assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+            assert_eq!(value, json!({"code": 412, "message": "precondition failed"}));
+        }
+        let weak = [(http::header::IF_MATCH, "W/\"1\"")];
+        let (status, _, _) = send(&router, "DELETE", "/todos/1", &alice, &weak, None).await;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+        let (status, headers, todo) = send(&router, "GET", "/todos/1", &alice, &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers[http::header::ETAG], "\"1\"");
+        assert_eq!(todo["title"], json!("buy milk"));
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_filterable_todos"))]
+    async fn etag_ok_changes_with_tags_and_items(pg_pool: PgPool) {
+        let router = app(pg_pool);
+        let alice = bearer(ALICE);
+
+        let (_, headers, _) = send(&router, "GET", "/todos/1", &alice, &[], None).await;
+        let etag = headers[http::header::ETAG].clone();
+        let body = Some(json!({"name": "work"}));
+        let (status, headers, _) = send(&router, "POST", "/todos/1/tags", &alice, &[], body).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_ne!(headers[http::header::ETAG], etag);
+        let if_none_match = [(http::header::IF_NONE_MATCH, etag.to_str().unwrap())];
+        let (status, _, todo) =
+            send(&router, "GET", "/todos/1", &alice, &if_none_match, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(todo["tags"], json!(["work"]));
+
+        let etag = headers[http::header::ETAG].to_str().unwrap();
+        let body = Some(json!({"title": "eggs"}));
+        send(&router, "POST", "/todos/1/items", &alice, &[], body).await;
+        let if_none_match = [(http::header::IF_NONE_MATCH, etag)];
+        let (status, _, todo) =
+            send(&router, "GET", "/todos/1", &alice, &if_none_match, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(todo["progress"], json!({"done": 0, "total": 1}));
+        let if_match = [(http::header::IF_MATCH, etag)];
+        let body = Some(json!({}));
+        let (status, _, _) = send(&router, "PATCH", "/todos/1", &alice, &if_match, body).await;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+        let body = Some(json!({"after": 2}));
+        let (status, moved, _) = send(&router, "POST", "/todos/1/move", &alice, &[], body).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(moved.contains_key(http::header::ETAG));
+        send(&router, "DELETE", "/todos/1", &alice, &[], None).await;
+        let (status, restored, _) =
+            send(&router, "POST", "/todos/1/restore", &alice, &[], None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(restored.contains_key(http::header::ETAG));
+        assert_ne!(restored[http::header::ETAG], moved[http::header::ETAG]);
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_filterable_todos"))]
+    async fn etag_ok_checks_tag_and_item_writes(pg_pool: PgPool) {
+        let router = app(pg_pool);
+        let alice = bearer(ALICE);
+        let version_2 = [(http::header::IF_MATCH, "\"2\"")];
+
+        let body = Some(json!({"title": "eggs"}));
+        let (status, _, _) =
+            send(&router, "POST", "/todos/1/items", &alice, &version_2, body).await;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+        let version_1 = [(http::header::IF_MATCH, "\"1\"")];
+        let body = Some(json!({"title": "eggs"}));
+        let (status, headers, item) =
+            send(&router, "POST", "/todos/1/items", &alice, &version_1, body).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(headers[http::header::ETAG], "\"2\"");
+
+        let uri = format!("/todos/1/items/{}", item["id"]);
+        let body = Some(json!({"done": true}));
+        let (status, _, _) = send(&router, "PATCH", &uri, &alice, &version_1, body).await;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+        let (status, headers, _) = send(&router, "DELETE", &uri, &alice, &version_2, None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert_eq!(headers[http::header::ETAG], "\"3\"");
+
+        let body = Some(json!({"name": "work"}));
+        send(&router, "POST", "/todos/1/tags", &alice, &[], body).await;
+        let (status, _, _) =
+            send(&router, "DELETE", "/todos/1/tags/work", &alice, &version_2, None).await;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+        let (status, headers, _) =
+            send(&router, "DELETE", "/todos/1/tags/work", &alice, &[], None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert_eq!(headers[http::header::ETAG], "\"5\"");
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn idempotency_key_ok_replays(pg_pool: PgPool) {
+        let router = app(pg_pool);
+        let alice = bearer(ALICE);
+        let retry_1 = [(IDEMPOTENCY_KEY, "retry-1")];
+        let body = json!({"title": "flaky", "content": ""});
+
+        let (status, headers, first) =
+            send(&router, "POST", "/todos", &alice, &retry_1, Some(body.clone())).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert!(!headers.contains_key(IDEMPOTENT_REPLAYED));
+        let (status, headers, retry) =
+            send(&router, "POST", "/todos", &alice, &retry_1, Some(body.clone())).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert!(headers.contains_key(IDEMPOTENT_REPLAYED));
+        assert_eq!(retry, first);
+
+        // Keys are per user, and requests without one are never replayed.
+        let (status, headers, _) =
+            send(&router, "POST", "/todos", &bearer(BOB), &retry_1, Some(body.clone())).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert!(!headers.contains_key(IDEMPOTENT_REPLAYED));
+        let (_, _, todos) = send(&router, "GET", "/todos", &alice, &[], None).await;
+        assert_eq!(todos["total"], json!(1));
+        send(&router, "POST", "/todos", &alice, &[], Some(body)).await;
+        let (_, _, todos) = send(&router, "GET", "/todos", &alice, &[], None).await;
+        assert_eq!(todos["total"], json!(2));
+
+        // Client errors are replayed as well.
+        let retry_2 = [(IDEMPOTENCY_KEY, "retry-2")];
+        let invalid = json!({"title": " ", "content": ""});
+        let (status, _, _) =
+            send(&router, "POST", "/todos", &alice, &retry_2, Some(invalid.clone())).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let (status, headers, _) =
+            send(&router, "POST", "/todos", &alice, &retry_2, Some(invalid)).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(headers.contains_key(IDEMPOTENT_REPLAYED));
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn idempotency_key_err(pg_pool: PgPool) {
+        let router = app(pg_pool.clone());
+        let alice = bearer(ALICE);
+        let retry_1 = [(IDEMPOTENCY_KEY, "retry-1")];
+        let body = json!({"title": "flaky", "content": ""});
+        send(&router, "POST", "/todos", &alice, &retry_1, Some(body.clone())).await;
+
+        let other = json!({"title": "other", "content": ""});
+        let (status, _, value) =
+            send(&router, "POST", "/todos", &alice, &retry_1, Some(other.clone())).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            value["errors"],
+            json!([{"field": "idempotency-key", "reason": "was already used for a different request"}])
+        );
+        let (status, _, _) =
+            send(&router, "PUT", "/todos/1", &alice, &retry_1, Some(other.clone())).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        let blank = [(IDEMPOTENCY_KEY, " ")];
+        let (status, _, value) = send(&router, "POST", "/todos", &alice, &blank, Some(other)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(value["path"], json!("idempotency-key"));
+
+        // As if the first request were still running.
+        sqlx::query("UPDATE idempotency_keys SET status_code = NULL, body = NULL;")
+            .execute(&pg_pool)
+            .await
+            .unwrap();
+        let (status, _, _) = send(&router, "POST", "/todos", &alice, &retry_1, Some(body)).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_filterable_todos"))]
+    async fn bulk_ok(pg_pool: PgPool) {
+        let router = app(pg_pool);
+        let body = json!({"operations": [
+            {"op": "create", "title": "imported", "content": ""},
+            {"op": "update", "id": 1, "completed": true, "due_at": null},
+            {"op": "delete", "id": 2},
+        ]});
+
+        let (status, _, value) =
+            send(&router, "POST", "/todos/bulk", &bearer(ALICE), &[], Some(body)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(value["committed"], json!(true));
+        let results = value["results"].as_array().unwrap();
+        assert_eq!(results[0]["status"], json!(201));
+        assert_eq!(results[0]["todo"]["title"], json!("imported"));
+        assert_eq!(results[1]["status"], json!(200));
+        assert_eq!(results[1]["todo"]["completed"], json!(true));
+        assert_eq!(results[1]["todo"]["due_at"], json!(null));
+        assert_eq!(results[2], json!({"status": 204}));
+
+        let (status, _, _) = send(&router, "GET", "/todos/2", &bearer(ALICE), &[], None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_filterable_todos"))]
+    async fn bulk_err_rolls_back_unless_partial(pg_pool: PgPool) {
+        let router = app(pg_pool);
+        let operations = json!([
+            {"op": "update", "id": 1, "title": "renamed"},
+            {"op": "update", "id": 2, "title": " "},
+            {"op": "delete", "id": 99},
+        ]);
+        let not_found = json!({"status": 404, "message": "not found"});
+        let invalid = json!({
+            "status": 422,
+            "message": "the following fields are invalid",
+            "errors": [{"field": "title", "reason": "must not be blank"}],
+        });
+
+        let body = json!({"operations": operations});
+        let (status, _, value) =
+            send(&router, "POST", "/todos/bulk", &bearer(ALICE), &[], Some(body.clone())).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(value["committed"], json!(false));
+        assert_eq!(value["results"][0]["status"], json!(424));
+        assert_eq!(value["results"][1], invalid);
+        assert_eq!(value["results"][2]["status"], json!(424));
+
+        let valid = json!({"operations": [operations[0], operations[2]]});
+        let (status, _, value) =
+            send(&router, "POST", "/todos/bulk", &bearer(ALICE), &[], Some(valid)).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(value["results"][0]["status"], json!(424));
+        assert_eq!(value["results"][1], not_found);
+        let (_, _, todo) = send(&router, "GET", "/todos/1", &bearer(ALICE), &[], None).await;
+        assert_eq!(todo["title"], json!("buy milk"));
+
+        let (status, _, value) = send(
+            &router,
+            "POST",
+            "/todos/bulk?partial=true",
+            &bearer(ALICE),
+            &[],
+            Some(body),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(value["committed"], json!(true));
+        assert_eq!(value["results"][0]["todo"]["title"], json!("renamed"));
+        assert_eq!(value["results"][1], invalid);
+        assert_eq!(value["results"][2], not_found);
+    }
+
+    #[sqlx::test(fixtures("mock_users", "mock_filterable_todos"))]
+    async fn bulk_ok_partial_reports_database_errors(pg_pool: PgPool) {
+        sqlx::query("ALTER TABLE todos ADD CONSTRAINT title_not_rejected CHECK (title <> 'rejected');")
+            .execute(&pg_pool)
+            .await
+            .unwrap();
+        let router = app(pg_pool);
+        let body = json!({"operations": [
+            {"op": "update", "id": 1, "title": "renamed"},
+            {"op": "create", "title": "rejected", "content": ""},
+        ]});
+
+        let (status, _, value) = send(
+            &router,
+            "POST",
+            "/todos/bulk?partial=true",
+            &bearer(ALICE),
+            &[],
+            Some(body),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(value["committed"], json!(true));
+        assert_eq!(value["results"][0]["status"], json!(200));
+        assert_eq!(value["results"][1], json!({"status": 500, "message": "internal server error"}));
+        let (_, _, todo) = send(&router, "GET", "/todos/1", &bearer(ALICE), &[], None).await;
+        assert_eq!(todo["title"], json!("renamed"));
+    }
+
+    #[sqlx::test(fixtures("mock_users"))]
+    async fn bulk_err_batch_size(pg_pool: PgPool) {
+        let router = app(pg_pool);
+        let delete = json!({"op": "delete", "id": 1});
+
+        for operations in [vec![], vec![delete; 101]] {
+            let body = json!({"operations": operations});
+            let (status, _, value) =
+                send(&router, "POST", "/todos/bulk", &bearer(ALICE), &[], Some(body)).await;
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+            assert_eq!(
+                value["errors"],
+                json!([{"field": "operations", "reason": "must contain between 1 and 100 operations"}])
+            );
+        }
+        let body = json!({"operations": [{"op": "rename", "id": 1}]});
+        let (status, _, _) =
+            send(&router, "POST", "/todos/bulk", &bearer(ALICE), &[], Some(body)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
 }
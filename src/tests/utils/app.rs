@@ -4,35 +4,35 @@ mod tests {
 
     use axum::{extract::State, routing::get, Router};
 
-    use crate::utils::app::*;
+    use crate::{configs::settings::*, utils::app::*};
 
     #[derive(Clone)]
     struct TestState {}
 
     #[test]
     fn build_listening_address_default_ok() {
-        assert_eq!(build_listening_address(), "0.0.0.0:3000");
+        assert_eq!(build_listening_address(&Settings::default().server), "0.0.0.0:3000");
     }
 
     #[test]
-    fn build_listening_address_with_env_ok() {
-        temp_env::with_vars(
-            [("PORT", Some("8080")), ("HOST", Some("localhost"))],
-            || {
-                assert_eq!(build_listening_address(), "localhost:8080");
-            },
-        );
+    fn build_listening_address_with_settings_ok() {
+        let settings = ServerSettings {
+            host: "localhost".to_string(),
+            port: 8080,
+            ..Settings::default().server
+        };
+        assert_eq!(build_listening_address(&settings), "localhost:8080");
     }
 
     #[test]
     fn build_router_empty_ok() {
-        let modules: Vec<(&str, fn() -> Router<Arc<TestState>>)> = vec![];
+        let modules: Vec<Module<TestState>> = vec![];
         let _ = build_router(modules);
     }
 
     #[test]
     fn build_router_ok() {
-        let mut modules: Vec<(&str, fn() -> Router<Arc<TestState>>)> = vec![];
+        let mut modules: Vec<Module<TestState>> = vec![];
 
         async fn test_handler(State(_): State<Arc<TestState>>) -> &'static str {
             "test"
@@ -45,10 +45,26 @@ mod tests {
         let _ = build_router(modules);
     }
 
+    #[test]
+    fn build_router_root_module_ok() {
+        async fn test_handler(State(_): State<Arc<TestState>>) -> &'static str {
+            "test"
+        }
+        fn root_router() -> Router<Arc<TestState>> {
+            Router::new().route("/healthz", get(test_handler))
+        }
+        fn test_router() -> Router<Arc<TestState>> {
+            Router::new().route("/", get(test_handler))
+        }
+
+        let modules: Vec<Module<TestState>> = vec![("/", root_router), ("/tests", test_router)];
+        let _ = build_router(modules);
+    }
+
     #[test]
     #[should_panic]
     fn build_router_err_duplicated_path() {
-        let mut modules: Vec<(&str, fn() -> Router<Arc<TestState>>)> = vec![];
+        let mut modules: Vec<Module<TestState>> = vec![];
 
         async fn test_handler(State(_): State<Arc<TestState>>) -> &'static str {
             "test"
@@ -66,7 +82,7 @@ mod tests {
     async fn build_listener_ok() {
         let listener = build_listener("localhost:9999".to_string()).await;
         let local_addr_result = listener.local_addr();
-        assert!(!local_addr_result.is_err());
+        assert!(local_addr_result.is_ok());
         let socket_address = local_addr_result.unwrap();
         assert_eq!(socket_address.port(), 9999);
     }
@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{extract::FromRequestParts, http::Request};
+    use sqlx::PgPool;
+
+    use crate::{
+        configs::state::AppState,
+        modules::users::models::Role,
+        utils::{
+            auth::{AuthUser, Credential},
+            error::AppError,
+        },
+    };
+
+    #[sqlx::test(migrations = false)]
+    async fn from_request_parts_ok_reuses_extension(pool: PgPool) {
+        let state = Arc::new(AppState::new(pool));
+        let (mut parts, _) = Request::new(()).into_parts();
+        assert!(matches!(
+            AuthUser::from_request_parts(&mut parts, &state).await,
+            Err(AppError::Unauthorized(_))
+        ));
+
+        parts.extensions.insert(AuthUser {
+            id: 7,
+            role: Role::Admin,
+            credential: Credential::AccessToken,
+        });
+        let auth_user = AuthUser::from_request_parts(&mut parts, &state).await.unwrap();
+        assert_eq!((auth_user.id, auth_user.role), (7, Role::Admin));
+    }
+}
@@ -0,0 +1,20 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::cursor::*;
+
+    #[test]
+    fn encode_decode_ok() {
+        let cursor = Cursor {
+            sort: "due_at:desc".to_string(),
+            value: "2030-01-01T00:00:00Z".to_string(),
+            id: 42,
+        };
+        assert_eq!(Cursor::decode(&cursor.encode()), Some(cursor));
+    }
+
+    #[test]
+    fn decode_err_malformed() {
+        assert_eq!(Cursor::decode("not a cursor"), None);
+        assert_eq!(Cursor::decode("e30"), None);
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use axum::{http::StatusCode, response::IntoResponse};
+    use http_body_util::BodyExt;
+    use rstest::*;
+
+    use crate::{
+        utils::error::*,
+        views::errors::{FieldViolation, UnauthorizedErrorMessage},
+    };
+
+    async fn into_parts(error: AppError) -> (StatusCode, String) {
+        let response = error.into_response();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, std::str::from_utf8(&body).unwrap().to_string())
+    }
+
+    #[rstest]
+    #[case(AppError::NotFound, StatusCode::NOT_FOUND, "{\"code\":404,\"message\":\"not found\"}")]
+    #[case(AppError::Conflict, StatusCode::CONFLICT, "{\"code\":409,\"message\":\"conflict\"}")]
+    #[case(AppError::Unauthorized(UnauthorizedErrorMessage::expired_token()), StatusCode::UNAUTHORIZED, "{\"code\":401,\"message\":\"access token has expired\",\"reason\":\"expired_token\"}")]
+    #[case(AppError::Forbidden, StatusCode::FORBIDDEN, "{\"code\":403,\"message\":\"forbidden\"}")]
+    #[case(AppError::Validation(vec![FieldViolation { field: "title".to_string(), reason: "must not be blank".to_string() }]), StatusCode::UNPROCESSABLE_ENTITY, "{\"code\":422,\"message\":\"the following fields are invalid\",\"errors\":[{\"field\":\"title\",\"reason\":\"must not be blank\"}]}")]
+    #[case(AppError::Database(sqlx::Error::PoolTimedOut), StatusCode::INTERNAL_SERVER_ERROR, "{\"code\":500,\"message\":\"internal server error\"}")]
+    #[case(AppError::Internal("boom".to_string()), StatusCode::INTERNAL_SERVER_ERROR, "{\"code\":500,\"message\":\"internal server error\"}")]
+    #[tokio::test]
+    async fn app_error_into_response_ok(
+        #[case] error: AppError,
+        #[case] status_code: StatusCode,
+        #[case] expected_body: &str,
+    ) {
+        let (status, body) = into_parts(error).await;
+        assert_eq!(status, status_code);
+        assert_eq!(body, expected_body);
+    }
+
+    #[test]
+    fn app_error_from_sqlx_error_ok() {
+        assert!(matches!(AppError::from(sqlx::Error::RowNotFound), AppError::NotFound));
+        assert!(matches!(
+            AppError::from(sqlx::Error::PoolTimedOut),
+            AppError::Database(sqlx::Error::PoolTimedOut)
+        ));
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use crate::{configs::settings::*, utils::jwt::*};
+
+    fn hs256_settings() -> AuthSettings {
+        AuthSettings {
+            secret: "0123456789abcdef0123456789abcdef".to_string(),
+            ..Settings::default().auth
+        }
+    }
+
+    fn hs256_keys() -> JwtKeys {
+        JwtKeys::new(&hs256_settings()).unwrap()
+    }
+
+    #[test]
+    fn issue_and_verify_ok() {
+        let keys = hs256_keys();
+        let token = keys.issue(7).unwrap();
+        let claims = keys.verify(&token).unwrap();
+        assert_eq!(claims.sub, "7");
+        assert_eq!(claims.iss, "todos-with-axum");
+        assert_eq!(claims.aud, "todos-with-axum");
+        assert_eq!(claims.exp - claims.iat, 15 * 60);
+    }
+
+    #[test]
+    fn issue_and_verify_rs256_ok() {
+        let keys = JwtKeys::new(&AuthSettings {
+            algorithm: JwtAlgorithm::Rs256,
+            private_key: fs::read_to_string("src/tests/fixtures/jwt_private_key.pem").unwrap(),
+            public_key: fs::read_to_string("src/tests/fixtures/jwt_public_key.pem").unwrap(),
+            ..Settings::default().auth
+        })
+        .unwrap();
+        let token = keys.issue(7).unwrap();
+        assert_eq!(keys.verify(&token).unwrap().sub, "7");
+        assert_eq!(hs256_keys().verify(&token), Err(TokenError::Invalid));
+    }
+
+    #[test]
+    fn verify_leeway_ok() {
+        let keys = JwtKeys::new(&AuthSettings {
+            leeway: Duration::from_secs(60),
+            ..hs256_settings()
+        })
+        .unwrap();
+        let mut claims = keys.claims_for(7);
+        claims.exp = claims.iat - 30;
+        assert!(keys.verify(&keys.encode(&claims).unwrap()).is_ok());
+
+        claims.exp = claims.iat - 90;
+        assert_eq!(keys.verify(&keys.encode(&claims).unwrap()), Err(TokenError::Expired));
+    }
+
+    #[test]
+    fn verify_err_invalid_claims() {
+        let keys = hs256_keys();
+
+        let mut claims = keys.claims_for(7);
+        claims.iss = "someone-else".to_string();
+        assert_eq!(keys.verify(&keys.encode(&claims).unwrap()), Err(TokenError::Invalid));
+
+        let mut claims = keys.claims_for(7);
+        claims.aud = "another-service".to_string();
+        assert_eq!(keys.verify(&keys.encode(&claims).unwrap()), Err(TokenError::Invalid));
+    }
+
+    #[test]
+    fn verify_err_tampered_token() {
+        let keys = hs256_keys();
+        let other_keys = JwtKeys::new(&AuthSettings {
+            secret: "fedcba9876543210fedcba9876543210".to_string(),
+            ..Settings::default().auth
+        })
+        .unwrap();
+
+        assert_eq!(keys.verify(&other_keys.issue(7).unwrap()), Err(TokenError::Invalid));
+        assert_eq!(keys.verify("not-a-token"), Err(TokenError::Invalid));
+    }
+}
@@ -1 +1,12 @@
-mod app;
\ No newline at end of file
+mod app;
+mod auth;
+mod cursor;
+mod error;
+mod jwt;
+mod password;
+mod patch;
+mod policy;
+mod shutdown;
+mod token;
+mod unit_of_work;
+mod validation;
\ No newline at end of file
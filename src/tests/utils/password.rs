@@ -0,0 +1,29 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::{error::AppError, password::*};
+
+    #[tokio::test]
+    async fn hash_and_verify_ok() {
+        let password_hash = hash("password123".to_string()).await.unwrap();
+        assert!(password_hash.starts_with("$argon2id$"));
+        assert_ne!(password_hash, hash("password123".to_string()).await.unwrap());
+        assert!(verify("password123".to_string(), password_hash.clone()).await.unwrap());
+        assert!(!verify("password124".to_string(), password_hash).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn dummy_hash_ok_matches_hash_parameters() {
+        let password_hash = hash("password123".to_string()).await.unwrap();
+        let parameters = |hash: &str| hash.rsplitn(3, '$').last().unwrap().to_string();
+        assert_eq!(parameters(DUMMY_HASH), parameters(&password_hash));
+        assert!(!verify("password123".to_string(), DUMMY_HASH.to_string()).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn verify_err_invalid_hash() {
+        assert!(matches!(
+            verify("password123".to_string(), "not-a-hash".to_string()).await,
+            Err(AppError::Internal(_))
+        ));
+    }
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::utils::patch::*;
+
+    #[derive(Deserialize)]
+    struct Document {
+        #[serde(default)]
+        value: Patch<i32>,
+    }
+
+    #[test]
+    fn deserialize_missing_ok() {
+        let document: Document = serde_json::from_str("{}").unwrap();
+        assert_eq!(document.value, Patch::Missing);
+        assert_eq!(document.value.into_option(), None);
+    }
+
+    #[test]
+    fn deserialize_null_ok() {
+        let document: Document = serde_json::from_str("{\"value\":null}").unwrap();
+        assert!(document.value.is_null());
+        assert_eq!(document.value.into_option(), Some(None));
+    }
+
+    #[test]
+    fn deserialize_value_ok() {
+        let document: Document = serde_json::from_str("{\"value\":1}").unwrap();
+        assert_eq!(document.value, Patch::Value(1));
+        assert_eq!(document.value.into_value(), Some(1));
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::{
+        modules::{api_keys::models::ApiKeyScope, users::models::Role},
+        utils::{
+            auth::{AuthUser, Credential},
+            error::AppError,
+            policy::*,
+        },
+    };
+
+    const READ_ONLY_KEY: Credential = Credential::ApiKey {
+        id: 1,
+        scope: ApiKeyScope::ReadOnly,
+    };
+    const READ_WRITE_KEY: Credential = Credential::ApiKey {
+        id: 1,
+        scope: ApiKeyScope::ReadWrite,
+    };
+
+    #[rstest]
+    #[case(Role::User, Credential::AccessToken, Permission::ReadOwnTodos, true)]
+    #[case(Role::User, Credential::AccessToken, Permission::WriteOwnTodos, true)]
+    #[case(Role::User, Credential::AccessToken, Permission::ManageApiKeys, true)]
+    #[case(Role::User, Credential::AccessToken, Permission::ReadAnyTodo, false)]
+    #[case(Role::User, Credential::AccessToken, Permission::ManageUsers, false)]
+    #[case(Role::User, READ_ONLY_KEY, Permission::ReadOwnTodos, true)]
+    #[case(Role::User, READ_ONLY_KEY, Permission::WriteOwnTodos, false)]
+    #[case(Role::User, READ_WRITE_KEY, Permission::WriteOwnTodos, true)]
+    #[case(Role::User, READ_WRITE_KEY, Permission::ManageApiKeys, false)]
+    #[case(Role::Admin, Credential::AccessToken, Permission::ReadAnyTodo, true)]
+    #[case(Role::Admin, Credential::AccessToken, Permission::ManageUsers, true)]
+    #[case(Role::Admin, READ_WRITE_KEY, Permission::ReadAnyTodo, false)]
+    #[case(Role::Admin, READ_WRITE_KEY, Permission::ManageUsers, false)]
+    fn is_allowed_ok(
+        #[case] role: Role,
+        #[case] credential: Credential,
+        #[case] permission: Permission,
+        #[case] expected: bool,
+    ) {
+        let auth_user = AuthUser {
+            id: 1,
+            role,
+            credential,
+        };
+        assert_eq!(is_allowed(&auth_user, permission), expected);
+    }
+
+    #[test]
+    fn authorize_err() {
+        let auth_user = AuthUser {
+            id: 1,
+            role: Role::User,
+            credential: Credential::AccessToken,
+        };
+        assert!(authorize(&auth_user, Permission::ReadOwnTodos).is_ok());
+        assert!(matches!(
+            authorize(&auth_user, Permission::ManageUsers),
+            Err(AppError::Forbidden)
+        ));
+    }
+}
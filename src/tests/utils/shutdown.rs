@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use std::{net::SocketAddr, time::Duration};
+
+    use axum::{routing::get, Router};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+        sync::oneshot,
+        task::JoinHandle,
+    };
+
+    use crate::utils::shutdown::*;
+
+    async fn slow_handler() -> &'static str {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        "done"
+    }
+
+    async fn start(drain_timeout: Duration) -> (SocketAddr, oneshot::Sender<()>, JoinHandle<bool>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let router = Router::new().route("/slow", get(slow_handler));
+        let (sender, receiver) = oneshot::channel::<()>();
+        let shutdown = async move {
+            let _ = receiver.await;
+        };
+        let server = tokio::spawn(async move {
+            serve(listener, router, shutdown, drain_timeout).await.unwrap()
+        });
+        (address, sender, server)
+    }
+
+    async fn request_slow(address: SocketAddr) -> String {
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream
+            .write_all(b"GET /slow HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn serve_drains_in_flight_requests_ok() {
+        let (address, sender, server) = start(Duration::from_secs(5)).await;
+        let request = tokio::spawn(request_slow(address));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        sender.send(()).unwrap();
+
+        let response = request.await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with("done"));
+        assert!(server.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn serve_stops_after_drain_timeout() {
+        let (address, sender, server) = start(Duration::from_millis(50)).await;
+        let request = tokio::spawn(request_slow(address));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        sender.send(()).unwrap();
+
+        assert!(!server.await.unwrap());
+        request.abort();
+    }
+
+    #[tokio::test]
+    async fn serve_rejects_connections_after_shutdown() {
+        let (address, sender, server) = start(Duration::from_secs(5)).await;
+        sender.send(()).unwrap();
+
+        assert!(server.await.unwrap());
+        assert!(TcpStream::connect(address).await.is_err());
+    }
+}
@@ -0,0 +1,23 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::token::*;
+
+    #[test]
+    fn generate_ok() {
+        let token = generate();
+        assert_eq!(token.len(), 43);
+        assert!(token
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || matches!(character, '-' | '_')));
+        assert_ne!(token, generate());
+    }
+
+    #[test]
+    fn hash_ok() {
+        assert_eq!(
+            hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_ne!(hash("abc"), hash("abd"));
+    }
+}
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use crate::utils::{error::AppError, unit_of_work};
+
+    async fn insert_user(transaction: &mut sqlx::PgConnection, email: &str) -> Result<(), AppError> {
+        sqlx::query("INSERT INTO users (email, password_hash) VALUES ($1, 'hash');")
+            .bind(email)
+            .execute(transaction)
+            .await?;
+        Ok(())
+    }
+
+    async fn count_users(pool: &PgPool) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM users;")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn run_ok_commits(pool: PgPool) {
+        let count = unit_of_work::run(&pool, |transaction| {
+            Box::pin(async move {
+                insert_user(transaction, "alice@example.com").await?;
+                insert_user(transaction, "bob@example.com").await?;
+                Ok(2)
+            })
+        })
+        .await
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(count_users(&pool).await, 2);
+    }
+
+    #[sqlx::test]
+    async fn run_err_rolls_back(pool: PgPool) {
+        let result = unit_of_work::run(&pool, |transaction| {
+            Box::pin(async move {
+                insert_user(transaction, "alice@example.com").await?;
+                Err::<(), _>(AppError::PreconditionFailed)
+            })
+        })
+        .await;
+        assert!(matches!(result, Err(AppError::PreconditionFailed)));
+        assert_eq!(count_users(&pool).await, 0);
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::{error::AppError, validation::*};
+
+    const RULES: &[Rule] = &[
+        Rule::Required,
+        Rule::NotBlank,
+        Rule::MaxLength(5),
+        Rule::AllowedCharacters(is_single_line, "printable characters"),
+    ];
+
+    fn reasons(result: Result<(), AppError>) -> Vec<(String, String)> {
+        match result {
+            Ok(_) => vec![],
+            Err(AppError::Validation(violations)) => violations
+                .into_iter()
+                .map(|violation| (violation.field, violation.reason))
+                .collect(),
+            Err(error) => panic!("expected: validation error, got: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn validator_ok() {
+        assert!(Validator::new().field("name", Some("foo"), RULES).finish().is_ok());
+    }
+
+    #[test]
+    fn validator_err_required() {
+        assert_eq!(
+            reasons(Validator::new().field("name", None, RULES).finish()),
+            vec![("name".to_string(), "is required".to_string())]
+        );
+    }
+
+    #[test]
+    fn validator_err_collects_all_violations() {
+        assert_eq!(
+            reasons(
+                Validator::new()
+                    .field("name", Some("   "), RULES)
+                    .field("nickname", Some("foo\u{7}bar"), RULES)
+                    .finish()
+            ),
+            vec![
+                ("name".to_string(), "must not be blank".to_string()),
+                ("nickname".to_string(), "must be at most 5 characters".to_string()),
+                ("nickname".to_string(), "must only contain printable characters".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn is_multi_line_ok() {
+        assert!("foo\n\tbar\r\n".chars().all(is_multi_line));
+        assert!(!"foo\u{0}".chars().all(is_multi_line));
+        assert!(!"foo\nbar".chars().all(is_single_line));
+    }
+
+    #[test]
+    fn validator_err_min_length_and_format() {
+        let rules: &[Rule] = &[Rule::MinLength(8), Rule::Format(is_email, "a valid email address")];
+        assert_eq!(
+            reasons(Validator::new().field("email", Some("foo@bar"), rules).finish()),
+            vec![
+                ("email".to_string(), "must be at least 8 characters".to_string()),
+                ("email".to_string(), "must be a valid email address".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn is_email_ok() {
+        assert!(is_email("alice@example.com"));
+        assert!(is_email("alice+todos@mail.example.org"));
+        assert!(!is_email("alice"));
+        assert!(!is_email("@example.com"));
+        assert!(!is_email("alice@example"));
+        assert!(!is_email("alice@@example.com"));
+        assert!(!is_email("alice@example..com"));
+        assert!(!is_email("alice smith@example.com"));
+    }
+
+    #[test]
+    fn is_hex_color_ok() {
+        assert!(is_hex_color("#1e90ff"));
+        assert!(is_hex_color("#1E90FF"));
+        assert!(!is_hex_color("1e90ff"));
+        assert!(!is_hex_color("#1e90f"));
+        assert!(!is_hex_color("#1e90fg"));
+        assert!(!is_hex_color("blue"));
+    }
+}
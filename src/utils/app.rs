@@ -1,22 +1,28 @@
-use std::env;
+use std::sync::Arc;
 
 use axum::Router;
 
-pub fn build_router<S>(modules: Vec<(&str, fn() -> Router<S>)>) -> Router<S>
+use crate::configs::settings::ServerSettings;
+
+pub type Module<S> = (&'static str, fn() -> Router<Arc<S>>);
+
+pub fn build_router<S>(modules: Vec<Module<S>>) -> Router<Arc<S>>
 where
-    S: Clone + Send + Sync + 'static,
+    S: Send + Sync + 'static,
 {
     let mut router = Router::new();
     for module in modules.iter() {
-        router = router.nest(module.0, module.1())
+        // axum refuses to nest at the root, so root modules are merged instead.
+        router = match module.0 {
+            "/" => router.merge(module.1()),
+            path => router.nest(path, module.1()),
+        }
     }
     router
 }
 
-pub fn build_listening_address() -> String {
-    let port = env::var("PORT").unwrap_or("3000".to_string());
-    let host = env::var("HOST").unwrap_or("0.0.0.0".to_string());
-    return format!("{}:{}", host, port);
+pub fn build_listening_address(settings: &ServerSettings) -> String {
+    format!("{}:{}", settings.host, settings.port)
 }
 
 pub async fn build_listener(address: String) -> tokio::net::TcpListener {
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header::AUTHORIZATION, request::Parts},
+};
+
+use crate::{
+    configs::state::AppState,
+    modules::{
+        api_keys::{self, models::ApiKeyScope},
+        users::{
+            models::{self, normalize_email, Role},
+            service::UserService,
+        },
+    },
+    utils::{error::AppError, jwt::TokenError, password, token},
+    views::errors::UnauthorizedErrorMessage,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Credential {
+    AccessToken,
+    ApiKey { id: i32, scope: ApiKeyScope },
+}
+
+/// The user making the request, authenticated with either a bearer access
+/// token (`Authorization: Bearer ...`) or a personal API key
+/// (`Authorization: ApiKey ...`). What the user may do is decided by
+/// `utils::policy`.
+///
+/// Middleware that already authenticated the request leaves the user in the
+/// request extensions, where the extractor picks it up again.
+#[derive(Clone)]
+pub struct AuthUser {
+    pub id: i32,
+    pub role: Role,
+    pub credential: Credential,
+}
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for AuthUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(auth_user) = parts.extensions.get::<AuthUser>() {
+            return Ok(auth_user.clone());
+        }
+        let (scheme, token) = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split_once(' '))
+            .map(|(scheme, token)| (scheme.to_lowercase(), token.trim()))
+            .ok_or(AppError::Unauthorized(UnauthorizedErrorMessage::missing_credentials()))?;
+
+        let (user_id, credential) = match scheme.as_str() {
+            "bearer" => from_access_token(state, token)?,
+            "apikey" => from_api_key(state, token).await?,
+            _ => {
+                return Err(AppError::Unauthorized(
+                    UnauthorizedErrorMessage::missing_credentials(),
+                ))
+            }
+        };
+
+        // Loading the user on every request makes disabling an account take
+        // effect immediately instead of when its access tokens expire.
+        let user = match UserService::new(state.clone()).find(user_id).await {
+            Err(sqlx::Error::RowNotFound) => {
+                return Err(AppError::Unauthorized(UnauthorizedErrorMessage::invalid_token()))
+            }
+            result => result?,
+        };
+        if user.is_disabled() {
+            return Err(AppError::Unauthorized(UnauthorizedErrorMessage::account_disabled()));
+        }
+        Ok(AuthUser {
+            id: user.id,
+            role: user.role,
+            credential,
+        })
+    }
+}
+
+fn from_access_token(state: &AppState, token: &str) -> Result<(i32, Credential), AppError> {
+    let claims = state.jwt.verify(token).map_err(|error| {
+        AppError::Unauthorized(match error {
+            TokenError::Expired => UnauthorizedErrorMessage::expired_token(),
+            TokenError::Invalid => UnauthorizedErrorMessage::invalid_token(),
+        })
+    })?;
+    match claims.sub.parse::<i32>() {
+        Ok(id) => Ok((id, Credential::AccessToken)),
+        Err(_) => Err(AppError::Unauthorized(UnauthorizedErrorMessage::invalid_token())),
+    }
+}
+
+async fn from_api_key(state: &AppState, key: &str) -> Result<(i32, Credential), AppError> {
+    match api_keys::service::authenticate(&state.db_pool, &token::hash(key)).await {
+        Ok(api_key) => Ok((
+            api_key.user_id,
+            Credential::ApiKey {
+                id: api_key.id,
+                scope: api_key.scope,
+            },
+        )),
+        Err(sqlx::Error::RowNotFound) => Err(AppError::Unauthorized(
+            UnauthorizedErrorMessage::invalid_api_key(),
+        )),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Returns the user owning `email` when `password` matches, `Unauthorized` otherwise.
+pub async fn authenticate(
+    state: Arc<AppState>,
+    email: &str,
+    password: String,
+) -> Result<models::User, AppError> {
+    let invalid_credentials =
+        || AppError::Unauthorized(UnauthorizedErrorMessage::invalid_credentials());
+    let user_service = UserService::new(state);
+    let user = match user_service.find_by_email(&normalize_email(email)).await {
+        Err(sqlx::Error::RowNotFound) => {
+            password::verify(password, password::DUMMY_HASH.to_string()).await?;
+            return Err(invalid_credentials());
+        }
+        result => result?,
+    };
+    if !password::verify(password, user.password_hash.clone()).await? {
+        return Err(invalid_credentials());
+    }
+    match user.is_disabled() {
+        true => Err(AppError::Unauthorized(UnauthorizedErrorMessage::account_disabled())),
+        false => Ok(user),
+    }
+}
@@ -1,11 +1,110 @@
-use axum::{body::Body, extract::rejection::PathRejection, http::{Response, StatusCode}};
+use std::error::Error;
 
-use crate::{constants::error_response::GENERIC_INTERNAL_SERVER_ERROR_RESPONSE, views::errors::from_error_kind};
+use axum::{
+    extract::rejection::{JsonRejection, PathRejection},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
 
-pub fn build_response_from_path_rejection(path: &str, path_rejection_error: PathRejection) -> (StatusCode, Response<Body>) {
+use crate::{
+    constants::error_response::{
+        JsonErrorMessage, GENERIC_CONFLICT_ERROR_RESPONSE, GENERIC_FORBIDDEN_ERROR_RESPONSE,
+        GENERIC_INTERNAL_SERVER_ERROR_RESPONSE, GENERIC_NOT_FOUND_ERROR_RESPONSE,
+        GENERIC_PRECONDITION_FAILED_ERROR_RESPONSE,
+    },
+    views::errors::{
+        from_error_kind, BadRequestErrorMessage, FieldViolation, UnauthorizedErrorMessage,
+        ValidationErrorMessage,
+    },
+};
+
+const UNIQUE_VIOLATION_CODE: &str = "23505";
+
+#[derive(Debug)]
+pub enum AppError {
+    NotFound,
+    Path(PathRejection),
+    Json(JsonRejection),
+    BadRequest(BadRequestErrorMessage),
+    Validation(Vec<FieldViolation>),
+    Conflict,
+    Unauthorized(UnauthorizedErrorMessage),
+    Forbidden,
+    PreconditionFailed,
+    Database(sqlx::Error),
+    Internal(String),
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        match error {
+            sqlx::Error::RowNotFound => AppError::NotFound,
+            sqlx::Error::Database(ref database_error)
+                if database_error.code().as_deref() == Some(UNIQUE_VIOLATION_CODE) =>
+            {
+                AppError::Conflict
+            }
+            error => AppError::Database(error),
+        }
+    }
+}
+
+impl From<PathRejection> for AppError {
+    fn from(path_rejection_error: PathRejection) -> Self {
+        AppError::Path(path_rejection_error)
+    }
+}
+
+impl From<JsonRejection> for AppError {
+    fn from(json_rejection_error: JsonRejection) -> Self {
+        AppError::Json(json_rejection_error)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        match self {
+            AppError::NotFound => GENERIC_NOT_FOUND_ERROR_RESPONSE.into_response().into_response(),
+            AppError::Path(path_rejection_error) => {
+                build_response_from_path_rejection(path_rejection_error).into_response()
+            }
+            AppError::Json(json_rejection_error) => {
+                build_response_from_json_rejection(json_rejection_error).into_response()
+            }
+            AppError::BadRequest(message) => (StatusCode::BAD_REQUEST, Json(message)).into_response(),
+            AppError::Validation(violations) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(ValidationErrorMessage::new(violations)),
+            )
+                .into_response(),
+            AppError::Conflict => GENERIC_CONFLICT_ERROR_RESPONSE.into_response().into_response(),
+            AppError::Unauthorized(message) => (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Bearer, ApiKey")],
+                Json(message),
+            )
+                .into_response(),
+            AppError::Forbidden => GENERIC_FORBIDDEN_ERROR_RESPONSE.into_response().into_response(),
+            AppError::PreconditionFailed => {
+                GENERIC_PRECONDITION_FAILED_ERROR_RESPONSE.into_response().into_response()
+            }
+            AppError::Database(error) => {
+                eprintln!("Database error found {}", error);
+                GENERIC_INTERNAL_SERVER_ERROR_RESPONSE.into_response().into_response()
+            }
+            AppError::Internal(message) => {
+                eprintln!("Internal error found {}", message);
+                GENERIC_INTERNAL_SERVER_ERROR_RESPONSE.into_response().into_response()
+            }
+        }
+    }
+}
+
+pub fn build_response_from_path_rejection(path_rejection_error: PathRejection) -> (StatusCode, Response) {
     match path_rejection_error {
             PathRejection::FailedToDeserializePathParams(error) => {
-                from_error_kind(path.to_string(), error.kind())
+                from_error_kind(error.kind())
             }
             PathRejection::MissingPathParams(error) => {
                 println!("Error found {}", error);
@@ -13,4 +112,78 @@ pub fn build_response_from_path_rejection(path: &str, path_rejection_error: Path
             }
             _ => GENERIC_INTERNAL_SERVER_ERROR_RESPONSE.into_response(),
         }
-}
\ No newline at end of file
+}
+
+
+pub fn build_response_from_json_rejection(json_rejection_error: JsonRejection) -> (StatusCode, Response) {
+    let (status_code, message) = match json_rejection_error {
+        JsonRejection::JsonDataError(ref error) => (
+            StatusCode::BAD_REQUEST,
+            match find_json_error(error) {
+                None => BadRequestErrorMessage::invalid_body(json_rejection_error.body_text()),
+                Some(error) => {
+                    let comment = strip_position(&error.inner().to_string());
+                    match missing_field_name(&comment) {
+                        Some(field) => BadRequestErrorMessage::missing_field(join_path(
+                            &error.path().to_string(),
+                            field,
+                        )),
+                        None => {
+                            BadRequestErrorMessage::invalid_field(error.path().to_string(), comment)
+                        }
+                    }
+                }
+            },
+        ),
+        JsonRejection::JsonSyntaxError(ref error) => (
+            StatusCode::BAD_REQUEST,
+            match find_json_error(error) {
+                None => BadRequestErrorMessage::invalid_body(json_rejection_error.body_text()),
+                Some(error) => BadRequestErrorMessage::invalid_body(error.inner().to_string()),
+            },
+        ),
+        JsonRejection::MissingJsonContentType(_) => (
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            BadRequestErrorMessage::unsupported_content_type(),
+        ),
+        _ => {
+            return JsonErrorMessage::new(
+                json_rejection_error.status(),
+                json_rejection_error.body_text(),
+            )
+            .into_response()
+        }
+    };
+    (status_code, Json(message).into_response())
+}
+
+fn find_json_error<'a>(error: &'a (dyn Error + 'static)) -> Option<&'a serde_path_to_error::Error<serde_json::Error>> {
+    let mut source = error.source();
+    while let Some(error) = source {
+        if let Some(json_error) = error.downcast_ref::<serde_path_to_error::Error<serde_json::Error>>() {
+            return Some(json_error);
+        }
+        source = error.source();
+    }
+    None
+}
+
+fn strip_position(message: &str) -> String {
+    match message.rsplit_once(" at line ") {
+        Some((message, _)) => message.to_string(),
+        None => message.to_string(),
+    }
+}
+
+fn missing_field_name(message: &str) -> Option<&str> {
+    message
+        .strip_prefix("missing field `")
+        .and_then(|field| field.strip_suffix('`'))
+}
+
+fn join_path(parent: &str, field: &str) -> String {
+    match parent {
+        "." => field.to_string(),
+        parent => format!("{}.{}", parent, field),
+    }
+}
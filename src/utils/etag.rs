@@ -0,0 +1,90 @@
+use std::convert::Infallible;
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{
+        header::{HeaderMap, HeaderName, ETAG, IF_MATCH, IF_NONE_MATCH},
+        request::Parts,
+        StatusCode,
+    },
+    Json,
+};
+
+use crate::utils::error::AppError;
+
+/// A response carrying the `ETag` of the resource it returns.
+pub type Tagged<T> = (StatusCode, [(HeaderName, String); 1], Json<T>);
+
+/// A response without a body, carrying the `ETag` of the resource it changed.
+pub type TaggedStatus = (StatusCode, [(HeaderName, String); 1]);
+
+pub fn tagged<T>(status_code: StatusCode, version: i32, body: T) -> Tagged<T> {
+    (status_code, header(version), Json(body))
+}
+
+pub fn header(version: i32) -> [(HeaderName, String); 1] {
+    [(ETAG, format!("\"{}\"", version))]
+}
+
+/// The `If-Match` and `If-None-Match` request headers, checked against the
+/// version of a resource that is exposed as its `ETag`.
+#[derive(Debug, Default)]
+pub struct Preconditions {
+    if_match: Option<Vec<String>>,
+    if_none_match: Option<Vec<String>>,
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Preconditions {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Preconditions {
+            if_match: entity_tags(&parts.headers, IF_MATCH),
+            if_none_match: entity_tags(&parts.headers, IF_NONE_MATCH),
+        })
+    }
+}
+
+impl Preconditions {
+    /// Fails with `PreconditionFailed` unless `If-Match` is absent or lists
+    /// the current version. Weak tags never match, as `If-Match` requires a
+    /// strong comparison.
+    ///
+    /// The version has to be read with `find_for_update` in the transaction
+    /// that makes the change, or the check is racy.
+    pub fn check_match(&self, version: i32) -> Result<(), AppError> {
+        let etag = format!("\"{}\"", version);
+        match &self.if_match {
+            None => Ok(()),
+            Some(tags) if tags.iter().any(|tag| tag == "*" || *tag == etag) => Ok(()),
+            Some(_) => Err(AppError::PreconditionFailed),
+        }
+    }
+
+    /// Whether `If-None-Match` lists the current version, in which case the
+    /// client's copy is still fresh and a GET answers 304 Not Modified.
+    pub fn is_not_modified(&self, version: i32) -> bool {
+        let etag = format!("\"{}\"", version);
+        match &self.if_none_match {
+            None => false,
+            Some(tags) => tags
+                .iter()
+                .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag),
+        }
+    }
+}
+
+fn entity_tags(headers: &HeaderMap, name: HeaderName) -> Option<Vec<String>> {
+    let mut values = headers.get_all(name).iter().peekable();
+    values.peek()?;
+    Some(
+        values
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+    )
+}
@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::{FromRequestParts, Request, State},
+    http::{request::Parts, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    configs::state::AppState,
+    constants::error_response::JsonErrorMessage,
+    modules::idempotency::{models::StoredResponse, service::IdempotencyService},
+    utils::{auth::AuthUser, error::AppError},
+    views::errors::{BadRequestErrorMessage, FieldViolation},
+};
+
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+const MAX_KEY_LENGTH: usize = 255;
+// Matches the default body limit of the `Json` extractor.
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Makes mutating requests that carry an `Idempotency-Key` header safe to
+/// retry: the first response is stored per user and key, and replayed for
+/// retries of the same request until the key expires. Reusing a key for a
+/// different request is rejected with 422, and a retry arriving while the
+/// original request is still running with 409, unless that request has held
+/// the key for longer than the lock timeout and is assumed to have died.
+///
+/// Responses with a 5xx status are not stored, so those requests run again.
+pub async fn replay(State(state): State<Arc<AppState>>, request: Request, next: Next) -> Response {
+    let is_mutating = matches!(
+        *request.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    );
+    if !is_mutating || !request.headers().contains_key(IDEMPOTENCY_KEY) {
+        return next.run(request).await;
+    }
+    let (mut parts, body) = request.into_parts();
+    // Unauthenticated requests are left to the handler to reject.
+    let Ok(auth_user) = AuthUser::from_request_parts(&mut parts, &state).await else {
+        return next.run(Request::from_parts(parts, body)).await;
+    };
+    // Spares the handler a second lookup of the same credentials.
+    parts.extensions.insert(auth_user.clone());
+    match run(state, auth_user, parts, body, next).await {
+        Ok(response) => response,
+        Err(error) => error.into_response(),
+    }
+}
+
+async fn run(
+    state: Arc<AppState>,
+    auth_user: AuthUser,
+    parts: Parts,
+    body: Body,
+    next: Next,
+) -> Result<Response, AppError> {
+    let key = parse_key(&parts)?;
+    let Ok(body) = to_bytes(body, MAX_BODY_BYTES).await else {
+        let message = "request body is too large".to_string();
+        return Ok(JsonErrorMessage::new(StatusCode::PAYLOAD_TOO_LARGE, message)
+            .into_response()
+            .into_response());
+    };
+    let fingerprint = fingerprint(&parts, &body);
+
+    let settings = &state.settings.idempotency;
+    let ttl = chrono::Duration::from_std(settings.ttl)
+        .map_err(|error| AppError::Internal(error.to_string()))?;
+    let lock_timeout = chrono::Duration::from_std(settings.lock_timeout)
+        .map_err(|error| AppError::Internal(error.to_string()))?;
+    let idempotency_service = IdempotencyService::new(state.clone(), auth_user.id);
+    idempotency_service.delete_expired(Utc::now() - ttl).await?;
+
+    if !idempotency_service
+        .claim(&key, &fingerprint, Utc::now() - lock_timeout)
+        .await?
+    {
+        // The key may have been released in between, which makes the
+        // original request look like it is still running.
+        let stored = match idempotency_service.find(&key).await {
+            Err(sqlx::Error::RowNotFound) => return Err(AppError::Conflict),
+            result => result?,
+        };
+        if stored.fingerprint != fingerprint {
+            return Err(AppError::Validation(vec![FieldViolation {
+                field: IDEMPOTENCY_KEY.to_string(),
+                reason: "was already used for a different request".to_string(),
+            }]));
+        }
+        return match (stored.status_code, stored.body) {
+            (Some(status_code), Some(body)) => Ok(rebuild(StoredResponse {
+                status_code,
+                headers: stored.headers,
+                body,
+            })),
+            _ => Err(AppError::Conflict),
+        };
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    if response.status().is_server_error() {
+        idempotency_service.release(&key).await?;
+        return Ok(response);
+    }
+    let (parts, body) = response.into_parts();
+    let body = to_bytes(body, usize::MAX)
+        .await
+        .map_err(|error| AppError::Internal(error.to_string()))?;
+    let stored = StoredResponse {
+        status_code: parts.status.as_u16() as i16,
+        headers: parts
+            .headers
+            .iter()
+            .filter_map(|(name, value)| Some(format!("{}: {}", name, value.to_str().ok()?)))
+            .collect(),
+        body: body.to_vec(),
+    };
+    idempotency_service.complete(&key, &stored).await?;
+
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+fn parse_key(parts: &Parts) -> Result<String, AppError> {
+    let key = parts
+        .headers
+        .get(IDEMPOTENCY_KEY)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .unwrap_or_default();
+    match key.is_empty() || key.chars().count() > MAX_KEY_LENGTH {
+        true => Err(AppError::BadRequest(BadRequestErrorMessage::invalid_header(
+            IDEMPOTENCY_KEY.to_string(),
+            format!("must be 1 to {} visible ASCII characters", MAX_KEY_LENGTH),
+        ))),
+        false => Ok(key.to_string()),
+    }
+}
+
+// Identifies the request a key was first used for, so that reusing the key
+// for anything else can be told apart from a retry.
+fn fingerprint(parts: &Parts, body: &Bytes) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(parts.method.as_str());
+    hasher.update(b"\n");
+    hasher.update(parts.uri.path_and_query().map_or("", |path| path.as_str()));
+    hasher.update(b"\n");
+    hasher.update(body);
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn rebuild(stored: StoredResponse) -> Response {
+    let status = StatusCode::from_u16(stored.status_code as u16).unwrap_or(StatusCode::OK);
+    let mut response = (status, stored.body).into_response();
+    let headers = response.headers_mut();
+    headers.clear();
+    for header in stored.headers.iter() {
+        let Some((name, value)) = header.split_once(": ") else {
+            continue;
+        };
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            headers.append(name, value);
+        }
+    }
+    headers.insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+    response
+}
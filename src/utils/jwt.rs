@@ -0,0 +1,107 @@
+use chrono::Utc;
+use jsonwebtoken::{errors::ErrorKind, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+use crate::configs::settings::{AuthSettings, JwtAlgorithm};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub iss: String,
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TokenError {
+    Expired,
+    Invalid,
+}
+
+/// Signs and verifies access tokens with the keys and claims from `AuthSettings`.
+#[derive(Clone)]
+pub struct JwtKeys {
+    algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    issuer: String,
+    audience: String,
+    access_token_ttl: i64,
+    leeway: u64,
+}
+
+impl JwtKeys {
+    pub fn new(settings: &AuthSettings) -> Result<Self, String> {
+        let (algorithm, encoding_key, decoding_key) = match settings.algorithm {
+            JwtAlgorithm::Hs256 => (
+                Algorithm::HS256,
+                EncodingKey::from_secret(settings.secret.as_bytes()),
+                DecodingKey::from_secret(settings.secret.as_bytes()),
+            ),
+            JwtAlgorithm::Rs256 => (
+                Algorithm::RS256,
+                EncodingKey::from_rsa_pem(settings.private_key.as_bytes())
+                    .map_err(|error| format!("JWT private key is invalid: {}", error))?,
+                DecodingKey::from_rsa_pem(settings.public_key.as_bytes())
+                    .map_err(|error| format!("JWT public key is invalid: {}", error))?,
+            ),
+        };
+        let keys = JwtKeys {
+            algorithm,
+            encoding_key,
+            decoding_key,
+            issuer: settings.issuer.clone(),
+            audience: settings.audience.clone(),
+            access_token_ttl: settings.access_token_ttl.as_secs() as i64,
+            leeway: settings.leeway.as_secs(),
+        };
+
+        // Parsing accepts any PEM, so a mismatched or wrong kind of key only
+        // shows up when signing or verifying.
+        match keys.issue(0).map(|token| keys.verify(&token)) {
+            Ok(Ok(_)) => Ok(keys),
+            _ => Err("JWT keys cannot sign and verify tokens, check the key pair".to_string()),
+        }
+    }
+
+    pub fn access_token_ttl(&self) -> i64 {
+        self.access_token_ttl
+    }
+
+    pub fn claims_for(&self, user_id: i32) -> Claims {
+        let now = Utc::now().timestamp();
+        Claims {
+            sub: user_id.to_string(),
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
+            iat: now,
+            exp: now + self.access_token_ttl,
+        }
+    }
+
+    pub fn encode(&self, claims: &Claims) -> Result<String, String> {
+        jsonwebtoken::encode(&Header::new(self.algorithm), claims, &self.encoding_key)
+            .map_err(|error| format!("could not sign access token: {}", error))
+    }
+
+    pub fn issue(&self, user_id: i32) -> Result<String, String> {
+        self.encode(&self.claims_for(user_id))
+    }
+
+    pub fn verify(&self, token: &str) -> Result<Claims, TokenError> {
+        let mut validation = Validation::new(self.algorithm);
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_audience(&[&self.audience]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        validation.leeway = self.leeway;
+
+        match jsonwebtoken::decode::<Claims>(token, &self.decoding_key, &validation) {
+            Ok(data) => Ok(data.claims),
+            Err(error) => match error.kind() {
+                ErrorKind::ExpiredSignature => Err(TokenError::Expired),
+                _ => Err(TokenError::Invalid),
+            },
+        }
+    }
+}
@@ -1,2 +1,16 @@
 pub mod error;
-pub mod app;
\ No newline at end of file
+pub mod app;
+pub mod auth;
+pub mod cursor;
+pub mod etag;
+pub mod idempotency;
+pub mod jwt;
+pub mod password;
+pub mod patch;
+pub mod policy;
+pub mod query;
+pub mod shutdown;
+pub mod token;
+pub mod trash;
+pub mod unit_of_work;
+pub mod validation;
\ No newline at end of file
@@ -0,0 +1,36 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+
+use super::error::AppError;
+
+// Verified against when a login names no account, so that unknown emails take
+// as long to reject as wrong passwords. Hashed with the parameters of `hash`.
+pub const DUMMY_HASH: &str =
+    "$argon2id$v=19$m=19456,t=2,p=1$k18HVlG3Y9bnL9HTviqzRA$fG7Mmvr7ApB5XM2Eeo366hLQDHmFmD493Z68OfA5N14";
+
+// Argon2 is deliberately slow, so hashing runs on the blocking pool instead of
+// stalling the async workers.
+pub async fn hash(password: String) -> Result<String, AppError> {
+    tokio::task::spawn_blocking(move || {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|error| AppError::Internal(format!("could not hash password: {}", error)))
+    })
+    .await
+    .map_err(|error| AppError::Internal(format!("password hashing task failed: {}", error)))?
+}
+
+pub async fn verify(password: String, password_hash: String) -> Result<bool, AppError> {
+    tokio::task::spawn_blocking(move || match PasswordHash::new(&password_hash) {
+        Err(error) => Err(AppError::Internal(format!("stored password hash is invalid: {}", error))),
+        Ok(hash) => Ok(Argon2::default()
+            .verify_password(password.as_bytes(), &hash)
+            .is_ok()),
+    })
+    .await
+    .map_err(|error| AppError::Internal(format!("password verification task failed: {}", error)))?
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Deserializer};
+
+#[derive(Debug, Default, PartialEq)]
+pub enum Patch<T> {
+    #[default]
+    Missing,
+    Null,
+    Value(T),
+}
+
+impl<T> Patch<T> {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Patch::Null)
+    }
+
+    pub fn as_option(&self) -> Option<T>
+    where
+        T: Copy,
+    {
+        match self {
+            Patch::Value(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn into_option(self) -> Option<Option<T>> {
+        match self {
+            Patch::Missing => None,
+            Patch::Null => Some(None),
+            Patch::Value(value) => Some(Some(value)),
+        }
+    }
+
+    pub fn into_value(self) -> Option<T> {
+        match self {
+            Patch::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Patch<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match Option::<T>::deserialize(deserializer)? {
+            None => Patch::Null,
+            Some(value) => Patch::Value(value),
+        })
+    }
+}
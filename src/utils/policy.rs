@@ -0,0 +1,43 @@
+use crate::{
+    modules::{api_keys::models::ApiKeyScope, users::models::Role},
+    utils::{
+        auth::{AuthUser, Credential},
+        error::AppError,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Permission {
+    ReadOwnTodos,
+    WriteOwnTodos,
+    ManageApiKeys,
+    ReadAnyTodo,
+    ManageUsers,
+}
+
+pub fn is_allowed(auth_user: &AuthUser, permission: Permission) -> bool {
+    let is_access_token = auth_user.credential == Credential::AccessToken;
+    match permission {
+        Permission::ReadOwnTodos => true,
+        Permission::WriteOwnTodos => !matches!(
+            auth_user.credential,
+            Credential::ApiKey {
+                scope: ApiKeyScope::ReadOnly,
+                ..
+            }
+        ),
+        // Keys must not be able to mint or manage other keys.
+        Permission::ManageApiKeys => is_access_token,
+        // Administration is never delegated to API keys.
+        Permission::ReadAnyTodo | Permission::ManageUsers => {
+            is_access_token && auth_user.role == Role::Admin
+        }
+    }
+}
+
+pub fn authorize(auth_user: &AuthUser, permission: Permission) -> Result<(), AppError> {
+    match is_allowed(auth_user, permission) {
+        true => Ok(()),
+        false => Err(AppError::Forbidden),
+    }
+}
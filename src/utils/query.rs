@@ -0,0 +1,27 @@
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use serde::de::DeserializeOwned;
+
+use crate::{utils::error::AppError, views::errors::BadRequestErrorMessage};
+
+pub struct Query<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let query = parts.uri.query().unwrap_or_default();
+        let deserializer = serde_html_form::Deserializer::from_bytes(query.as_bytes());
+        match serde_path_to_error::deserialize(deserializer) {
+            Ok(value) => Ok(Query(value)),
+            Err(error) => Err(AppError::BadRequest(BadRequestErrorMessage::invalid_query(
+                error.path().to_string(),
+                error.inner().to_string(),
+            ))),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::{future::Future, future::IntoFuture, io, time::Duration};
+
+use axum::Router;
+use tokio::{net::TcpListener, sync::oneshot};
+
+// Resolves on the first SIGINT (Ctrl+C) or, on unix, SIGTERM.
+pub async fn signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install the Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install the SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Serves `router` until `shutdown` resolves, then stops accepting connections
+/// and waits up to `drain_timeout` for in-flight requests to finish.
+///
+/// Returns `Ok(false)` when the drain timeout elapsed with requests still running.
+pub async fn serve<F>(
+    listener: TcpListener,
+    router: Router,
+    shutdown: F,
+    drain_timeout: Duration,
+) -> io::Result<bool>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let (draining_sender, draining_receiver) = oneshot::channel::<()>();
+    let server = axum::serve(listener, router).with_graceful_shutdown(async move {
+        shutdown.await;
+        let _ = draining_sender.send(());
+    });
+    let deadline = async move {
+        if draining_receiver.await.is_ok() {
+            tokio::time::sleep(drain_timeout).await;
+        } else {
+            std::future::pending::<()>().await;
+        }
+    };
+
+    tokio::select! {
+        result = server.into_future() => result.map(|_| true),
+        _ = deadline => Ok(false),
+    }
+}
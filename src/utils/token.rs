@@ -0,0 +1,21 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use sha2::{Digest, Sha256};
+
+const TOKEN_BYTES: usize = 32;
+
+/// Returns a random, URL-safe opaque token.
+pub fn generate() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+// Opaque tokens carry enough entropy that a fast hash is sufficient; only the
+// hash is ever stored.
+pub fn hash(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
@@ -0,0 +1,26 @@
+use chrono::Utc;
+use sqlx::Postgres;
+use tokio::task::JoinHandle;
+
+use crate::configs::settings::TrashSettings;
+use crate::modules::todos::service::purge_trash;
+
+/// Spawns a task that permanently deletes trashed todos once they are older
+/// than the configured retention, checking every `purge_interval`.
+pub fn spawn_purge(db_pool: sqlx::Pool<Postgres>, settings: TrashSettings) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let Ok(retention) = chrono::Duration::from_std(settings.retention) else {
+            eprintln!("Trash retention is out of range, not purging");
+            return;
+        };
+        let mut interval = tokio::time::interval(settings.purge_interval);
+        loop {
+            interval.tick().await;
+            match purge_trash(&db_pool, Utc::now() - retention).await {
+                Ok(0) => {}
+                Ok(purged) => println!("Purged {} todos from the trash", purged),
+                Err(error) => eprintln!("Failed to purge the trash: {}", error),
+            }
+        }
+    })
+}
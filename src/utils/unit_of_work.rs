@@ -0,0 +1,35 @@
+use std::{future::Future, pin::Pin};
+
+use sqlx::{Postgres, Transaction};
+
+use crate::utils::error::AppError;
+
+/// The body of a unit of work, borrowing its transaction.
+pub type Work<'t, T> = Pin<Box<dyn Future<Output = Result<T, AppError>> + Send + 't>>;
+
+/// Runs `work` in one transaction that is committed when the work succeeds
+/// and rolled back when it fails, so that a handler can combine several
+/// service calls atomically:
+///
+/// ```ignore
+/// unit_of_work::run(&state.db_pool, |transaction| Box::pin(async move {
+///     let todo = todo_service.find_for_update(&mut **transaction, id).await?;
+///     Ok(todo_service.update(&mut **transaction, todo.id, &fields).await?)
+/// }))
+/// ```
+pub async fn run<T, F>(db_pool: &sqlx::Pool<Postgres>, work: F) -> Result<T, AppError>
+where
+    F: for<'t> FnOnce(&'t mut Transaction<'static, Postgres>) -> Work<'t, T>,
+{
+    let mut transaction = db_pool.begin().await?;
+    match work(&mut transaction).await {
+        Ok(value) => {
+            transaction.commit().await?;
+            Ok(value)
+        }
+        Err(error) => {
+            transaction.rollback().await?;
+            Err(error)
+        }
+    }
+}
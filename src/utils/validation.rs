@@ -0,0 +1,118 @@
+use crate::{
+    utils::{error::AppError, patch::Patch},
+    views::errors::FieldViolation,
+};
+
+pub enum Rule {
+    Required,
+    NotBlank,
+    MinLength(usize),
+    MaxLength(usize),
+    AllowedCharacters(fn(char) -> bool, &'static str),
+    Format(fn(&str) -> bool, &'static str),
+}
+
+impl Rule {
+    fn check(&self, value: Option<&str>) -> Option<String> {
+        match (self, value) {
+            (Rule::Required, None) => Some("is required".to_string()),
+            (Rule::NotBlank, Some(value)) if value.trim().is_empty() => {
+                Some("must not be blank".to_string())
+            }
+            (Rule::MinLength(min), Some(value)) if value.chars().count() < *min => {
+                Some(format!("must be at least {} characters", min))
+            }
+            (Rule::MaxLength(max), Some(value)) if value.chars().count() > *max => {
+                Some(format!("must be at most {} characters", max))
+            }
+            (Rule::AllowedCharacters(is_allowed, description), Some(value))
+                if !value.chars().all(is_allowed) =>
+            {
+                Some(format!("must only contain {}", description))
+            }
+            (Rule::Format(is_valid, description), Some(value)) if !is_valid(value) => {
+                Some(format!("must be {}", description))
+            }
+            _ => None,
+        }
+    }
+}
+
+pub trait Validate {
+    fn validate(&self) -> Result<(), AppError>;
+}
+
+#[derive(Default)]
+pub struct Validator {
+    violations: Vec<FieldViolation>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn field(mut self, name: &str, value: Option<&str>, rules: &[Rule]) -> Self {
+        for rule in rules.iter() {
+            if let Some(reason) = rule.check(value) {
+                self.violations.push(FieldViolation {
+                    field: name.to_string(),
+                    reason,
+                });
+            }
+        }
+        self
+    }
+
+    pub fn patch_field(self, name: &str, value: &Patch<String>, rules: &[Rule]) -> Self {
+        match value {
+            Patch::Value(value) => self.field(name, Some(value), rules),
+            _ => self,
+        }
+    }
+
+    pub fn not_null<T>(mut self, name: &str, value: &Patch<T>) -> Self {
+        if value.is_null() {
+            self.violations.push(FieldViolation {
+                field: name.to_string(),
+                reason: "must not be null".to_string(),
+            });
+        }
+        self
+    }
+
+    pub fn finish(self) -> Result<(), AppError> {
+        match self.violations.is_empty() {
+            true => Ok(()),
+            false => Err(AppError::Validation(self.violations)),
+        }
+    }
+}
+
+pub fn is_single_line(character: char) -> bool {
+    !character.is_control()
+}
+
+pub fn is_multi_line(character: char) -> bool {
+    !character.is_control() || matches!(character, '\n' | '\r' | '\t')
+}
+
+pub fn is_email(value: &str) -> bool {
+    match value.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.split('.').count() >= 2
+                && domain.split('.').all(|label| !label.is_empty())
+                && !value.chars().any(|character| character.is_whitespace() || character.is_control())
+        }
+        None => false,
+    }
+}
+
+pub fn is_hex_color(value: &str) -> bool {
+    match value.strip_prefix('#') {
+        Some(digits) => digits.len() == 6 && digits.chars().all(|digit| digit.is_ascii_hexdigit()),
+        None => false,
+    }
+}
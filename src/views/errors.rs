@@ -6,7 +6,7 @@ use axum::response::IntoResponse;
 use axum::{http::StatusCode, Json};
 use serde::Serialize;
 
-#[derive(Serialize)]
+#[derive(Debug, Serialize)]
 pub struct BadRequestErrorMessage {
     code: u16,
     message: String,
@@ -14,6 +14,77 @@ pub struct BadRequestErrorMessage {
     comment: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct FieldViolation {
+    pub field: String,
+    pub reason: String,
+}
+
+#[derive(Serialize)]
+pub struct ValidationErrorMessage {
+    code: u16,
+    message: String,
+    errors: Vec<FieldViolation>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnauthorizedErrorMessage {
+    code: u16,
+    message: String,
+    reason: String,
+}
+
+impl UnauthorizedErrorMessage {
+    fn new(message: &str, reason: &str) -> UnauthorizedErrorMessage {
+        UnauthorizedErrorMessage {
+            code: 401,
+            message: message.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn missing_credentials() -> UnauthorizedErrorMessage {
+        UnauthorizedErrorMessage::new("authentication is required", "missing_credentials")
+    }
+
+    pub fn invalid_credentials() -> UnauthorizedErrorMessage {
+        UnauthorizedErrorMessage::new("email or password is incorrect", "invalid_credentials")
+    }
+
+    pub fn invalid_token() -> UnauthorizedErrorMessage {
+        UnauthorizedErrorMessage::new("access token is invalid", "invalid_token")
+    }
+
+    pub fn expired_token() -> UnauthorizedErrorMessage {
+        UnauthorizedErrorMessage::new("access token has expired", "expired_token")
+    }
+
+    pub fn invalid_api_key() -> UnauthorizedErrorMessage {
+        UnauthorizedErrorMessage::new("api key is invalid or revoked", "invalid_api_key")
+    }
+
+    pub fn account_disabled() -> UnauthorizedErrorMessage {
+        UnauthorizedErrorMessage::new("account is disabled", "account_disabled")
+    }
+
+    pub fn invalid_refresh_token() -> UnauthorizedErrorMessage {
+        UnauthorizedErrorMessage::new(
+            "refresh token is invalid, expired or revoked",
+            "invalid_refresh_token",
+        )
+    }
+}
+
+impl ValidationErrorMessage {
+    pub fn new(errors: Vec<FieldViolation>) -> ValidationErrorMessage {
+        ValidationErrorMessage {
+            code: 422,
+            message: "the following fields are invalid".to_string(),
+            errors,
+        }
+    }
+}
+
 impl BadRequestErrorMessage {
     fn invalid_type(path: String, expected_type: String) -> BadRequestErrorMessage {
         BadRequestErrorMessage {
@@ -27,6 +98,60 @@ impl BadRequestErrorMessage {
         }
     }
 
+    pub fn missing_field(path: String) -> BadRequestErrorMessage {
+        BadRequestErrorMessage {
+            code: 400,
+            message: "the following field is missing".to_string(),
+            path,
+            comment: "field is required".to_string(),
+        }
+    }
+
+    pub fn invalid_field(path: String, comment: String) -> BadRequestErrorMessage {
+        BadRequestErrorMessage {
+            code: 400,
+            message: "type of the following field is invalid".to_string(),
+            path,
+            comment,
+        }
+    }
+
+    pub fn invalid_body(comment: String) -> BadRequestErrorMessage {
+        BadRequestErrorMessage {
+            code: 400,
+            message: "request body is not valid json".to_string(),
+            path: "body".to_string(),
+            comment,
+        }
+    }
+
+    pub fn invalid_query(path: String, comment: String) -> BadRequestErrorMessage {
+        BadRequestErrorMessage {
+            code: 400,
+            message: "the following query parameter is invalid".to_string(),
+            path,
+            comment,
+        }
+    }
+
+    pub fn invalid_header(path: String, comment: String) -> BadRequestErrorMessage {
+        BadRequestErrorMessage {
+            code: 400,
+            message: "the following header is invalid".to_string(),
+            path,
+            comment,
+        }
+    }
+
+    pub fn unsupported_content_type() -> BadRequestErrorMessage {
+        BadRequestErrorMessage {
+            code: 415,
+            message: "content type of the request is not supported".to_string(),
+            path: "content-type".to_string(),
+            comment: format!("expected: {}", mime::APPLICATION_JSON),
+        }
+    }
+
     fn get_type_description(type_name: String) -> String {
         if type_name.contains('u') {
             return "unsigned interger".to_string();
@@ -42,15 +167,28 @@ impl BadRequestErrorMessage {
     }
 }
 
-pub fn from_error_kind(path: String, error_kind: &ErrorKind) -> (StatusCode, Response<Body>) {
+pub fn from_error_kind(error_kind: &ErrorKind) -> (StatusCode, Response<Body>) {
     match error_kind {
-        ErrorKind::ParseError {
+        ErrorKind::ParseErrorAtKey {
+            key,
+            value: _,
+            expected_type,
+        } => (
+            StatusCode::BAD_REQUEST,
+            Json(BadRequestErrorMessage::invalid_type(
+                key.to_string(),
+                (*expected_type).to_string(),
+            ))
+            .into_response(),
+        ),
+        ErrorKind::ParseErrorAtIndex {
+            index,
             value: _,
             expected_type,
         } => (
             StatusCode::BAD_REQUEST,
             Json(BadRequestErrorMessage::invalid_type(
-                path,
+                index.to_string(),
                 (*expected_type).to_string(),
             ))
             .into_response(),
@@ -4,7 +4,28 @@ use serde::Serialize;
 #[derive(Serialize)]
 pub struct Pagination<T> {
     pub limit: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requested_limit: Option<i64>,
     pub offset: i64,
-    pub total: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
     pub items: Vec<T>
-}
\ No newline at end of file
+}
+
+#[derive(Serialize)]
+pub struct CursorPagination<T> {
+    pub limit: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requested_limit: Option<i64>,
+    pub next_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+    pub items: Vec<T>
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum Page<T> {
+    Offset(Pagination<T>),
+    Cursor(CursorPagination<T>),
+}